@@ -1,5 +1,6 @@
 use crate::{
-    ast::{expr, stmt},
+    ast::{expr, stmt, AstId},
+    interpreter::Interpreter,
     parser::scanner::Scanner,
 };
 
@@ -17,16 +18,26 @@ pub fn print_scanned_tokens(src: &str) {
 /// Prints the given program tree.
 pub fn print_program_tree(stmts: &[stmt::Stmt]) {
     println!("┌─");
-    TreePrinter::new("│ ").print_stmts(stmts);
+    TreePrinter::new("│ ", None).print_stmts(stmts);
     println!("└─");
 }
 
-struct TreePrinter {
+/// Prints the given program tree the same way `print_program_tree` does, but with every binding
+/// use (`var`, `this`, and assignment targets) annotated with the scope distance the `Resolver`
+/// computed for it, or `unresolved (global)` if the resolver left it to be looked up dynamically.
+pub fn print_resolved_tree(stmts: &[stmt::Stmt], interpreter: &Interpreter) {
+    println!("┌─");
+    TreePrinter::new("│ ", Some(interpreter)).print_stmts(stmts);
+    println!("└─");
+}
+
+struct TreePrinter<'i> {
     prefix: &'static str,
     level: usize,
+    interpreter: Option<&'i Interpreter>,
 }
 
-impl TreePrinter {
+impl<'i> TreePrinter<'i> {
     fn print_stmts(&mut self, stmts: &[stmt::Stmt]) {
         for (i, stmt) in stmts.iter().enumerate() {
             self.print_stmt(stmt);
@@ -59,7 +70,12 @@ impl TreePrinter {
                     s.emit("Methods");
                     s.nest(|s| {
                         for method in &class.methods {
-                            s.print_fun(method, "Class Method");
+                            let label = match method.kind {
+                                stmt::MethodKind::Function => "Class Method",
+                                stmt::MethodKind::Static => "Static Method",
+                                stmt::MethodKind::Getter => "Getter",
+                            };
+                            s.print_fun(method, label);
                         }
                     });
                 });
@@ -93,6 +109,8 @@ impl TreePrinter {
                     self.nest(|s| s.print_expr(value));
                 }
             }
+            Break(_) => self.emit("Break Stmt"),
+            Continue(_) => self.emit("Continue Stmt"),
             Print(print) => {
                 self.emit("Print Stmt");
                 self.nest(|s| {
@@ -119,11 +137,15 @@ impl TreePrinter {
             Lit(expr::Lit { value, .. }) => {
                 self.emit(format!("Literal ({:?} :: {})", value, value.type_name()));
             }
-            This(_) => {
-                self.emit("This");
+            This(this) => {
+                self.emit(format!("This{}", self.resolution_suffix(this.name.id)));
             }
             Var(var) => {
-                self.emit(format!("Var `{}`", var.name));
+                self.emit(format!(
+                    "Var `{}`{}",
+                    var.name,
+                    self.resolution_suffix(var.name.id)
+                ));
             }
             Group(group) => {
                 self.emit("Group");
@@ -182,11 +204,16 @@ impl TreePrinter {
             Assignment(assignment) => {
                 self.emit("Assignment");
                 self.nest(|s| {
-                    s.emit(format!("Target: `{}`", assignment.name));
+                    s.emit(format!(
+                        "Target: `{}`{}",
+                        assignment.name,
+                        s.resolution_suffix(assignment.name.id)
+                    ));
                     s.emit("With Value");
                     s.nest(|s| s.print_expr(&assignment.value));
                 });
             }
+            Error(_) => self.emit("Error Expr (INVALID TREE)"),
         }
     }
 
@@ -205,8 +232,24 @@ impl TreePrinter {
         });
     }
 
-    fn new(prefix: &'static str) -> Self {
-        Self { level: 0, prefix }
+    fn new(prefix: &'static str, interpreter: Option<&'i Interpreter>) -> Self {
+        Self {
+            level: 0,
+            prefix,
+            interpreter,
+        }
+    }
+
+    /// Formats `" :: depth N"`/`" :: unresolved (global)"` for a binding use, or an empty string
+    /// when not printing in `--resolve` mode.
+    fn resolution_suffix(&self, id: AstId) -> String {
+        match self.interpreter {
+            Some(interpreter) => match interpreter.local_depth(id) {
+                Some(depth) => format!(" :: depth {}", depth),
+                None => " :: unresolved (global)".to_string(),
+            },
+            None => String::new(),
+        }
     }
 
     fn emit(&self, str: impl Into<String>) {
@@ -21,7 +21,7 @@ impl Expr {
 
 make_ast_enum!(
     ExprKind,
-    [Lit, This, Var, Group, Super, Get, Set, Call, Unary, Binary, Logical, Assignment]
+    [Lit, This, Var, Group, Super, Get, Set, Call, Unary, Binary, Logical, Assignment, Error]
 );
 
 #[derive(Debug, Clone)]
@@ -95,6 +95,13 @@ pub struct Assignment {
     pub value: Box<Expr>,
 }
 
+/// For error purposes. Mirrors `stmt::Dummy`, but at expression granularity: synthesized by
+/// `Parser::parse_primary` when no production matches the current token, so a caller expecting an
+/// expression (a binary operand, a call argument, a var initializer, ...) can keep going instead
+/// of unwinding the whole enclosing statement to `stmt::Dummy`.
+#[derive(Debug, Clone)]
+pub struct Error();
+
 //
 // Some other utilities.
 //
@@ -105,7 +112,7 @@ impl From<Token> for Lit {
         use TokenKind as T;
         Lit {
             value: match token.kind {
-                T::String(string) => L::String(string),
+                T::String(string) => L::String(string.resolve().to_owned()),
                 T::Number(number) => L::Number(number),
                 T::Nil => L::Nil,
                 T::True => L::Boolean(true),
@@ -17,7 +17,9 @@ impl Stmt {
 
 make_ast_enum!(
     StmtKind,
-    [VarDecl, ClassDecl, FunDecl, If, While, Return, Print, Block, Expr, Dummy]
+    [
+        VarDecl, ClassDecl, FunDecl, If, While, Return, Break, Continue, Print, Block, Expr, Dummy
+    ]
 );
 
 #[derive(Debug, Clone)]
@@ -40,6 +42,23 @@ pub struct FunDecl {
     pub body: Vec<Stmt>,
     /// Span of the function parameters and body. Must NOT include, for example, the `fun` token.
     pub span: Span,
+    /// Always `MethodKind::Function` for a top-level `fun` declaration; set by `Parser::parse_method`
+    /// for a class member, which is the only place the other variants can arise.
+    pub kind: MethodKind,
+}
+
+/// Distinguishes the three forms a class member declaration can take. A plain top-level `fun`
+/// declaration is always `Function`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MethodKind {
+    /// An ordinary instance method, or a top-level function.
+    Function,
+    /// A method declared with a leading `class` keyword (`class named() { ... }`), dispatched on
+    /// the class itself rather than on an instance, and without access to `this`.
+    Static,
+    /// A getter (`named { ... }`): no parameter list, invoked like a property read rather than a
+    /// call.
+    Getter,
 }
 
 #[derive(Debug, Clone)]
@@ -61,6 +80,16 @@ pub struct Return {
     pub value: Option<expr::Expr>,
 }
 
+#[derive(Debug, Clone)]
+pub struct Break {
+    pub break_span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct Continue {
+    pub continue_span: Span,
+}
+
 #[derive(Debug, Clone)]
 pub struct Print {
     pub expr: expr::Expr,
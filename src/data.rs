@@ -6,12 +6,16 @@ use std::{
 };
 
 use crate::{
-    ast::{stmt::FunDecl, AstId},
+    ast::{
+        stmt::{FunDecl, MethodKind},
+        AstId,
+    },
     interpreter::{
         control_flow::ControlFlow, environment::Environment, error::RuntimeError, CFResult,
         Interpreter,
     },
     span::Span,
+    symbol::Symbol,
     token::{Token, TokenKind},
 };
 
@@ -72,13 +76,13 @@ impl Debug for LoxValue {
 
 #[derive(Debug, Clone)]
 pub struct LoxIdent {
-    pub name: String,
+    pub name: Symbol,
     pub span: Span,
     pub id: AstId,
 }
 
 impl LoxIdent {
-    pub fn new(span: Span, name: impl Into<String>) -> Self {
+    pub fn new(span: Span, name: &str) -> Self {
         LoxIdent {
             id: AstId::new(),
             name: name.into(),
@@ -90,7 +94,11 @@ impl LoxIdent {
 impl From<Token> for LoxIdent {
     fn from(Token { kind, span }: Token) -> Self {
         match kind {
-            TokenKind::Identifier(name) => LoxIdent::new(span, name),
+            TokenKind::Identifier(name) => LoxIdent {
+                id: AstId::new(),
+                name,
+                span,
+            },
             unexpected => unreachable!(
                 "Invalid `Token` ({:?}) to `LoxIdent` conversion.",
                 unexpected
@@ -101,13 +109,13 @@ impl From<Token> for LoxIdent {
 
 impl AsRef<str> for LoxIdent {
     fn as_ref(&self) -> &str {
-        &self.name
+        self.name.resolve()
     }
 }
 
 impl Display for LoxIdent {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(&self.name)
+        self.name.fmt(f)
     }
 }
 
@@ -115,6 +123,19 @@ pub trait LoxCallable: Display + Debug {
     fn call(self: Rc<Self>, interpreter: &mut Interpreter, args: &[LoxValue])
         -> CFResult<LoxValue>;
     fn arity(&self) -> usize;
+
+    /// Recovers the concrete `LoxClass` behind a `dyn LoxCallable`, if there is one. Used to
+    /// resolve a `super` expression's superclass, since a superclass name is just looked up as an
+    /// ordinary variable and therefore evaluates to a `LoxValue::Function`.
+    fn as_class(&self) -> Option<&LoxClass> {
+        None
+    }
+
+    /// Whether this callable is a getter, i.e. should be invoked immediately on property access
+    /// rather than returned as a bound method. Used by `Interpreter::eval_get_expr`.
+    fn is_getter(&self) -> bool {
+        false
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -127,10 +148,9 @@ pub struct LoxFunction {
 impl LoxFunction {
     pub fn bind(&self, instance: &Rc<LoxInstance>) -> Rc<Self> {
         let mut env = Environment::new_enclosed(&self.closure);
-        env.define(
-            LoxIdent::new(Span::new(0, 0), "this"),
-            LoxValue::Object(instance.clone()),
-        );
+        // `this` is always the sole (hence slot 0) binding of this scope, mirroring the
+        // resolver's `initialize("this")`.
+        env.define_at_slot(0, LoxValue::Object(instance.clone()));
         Rc::new(LoxFunction {
             decl: self.decl.clone(),
             closure: env,
@@ -146,8 +166,14 @@ impl LoxCallable for LoxFunction {
         args: &[LoxValue],
     ) -> CFResult<LoxValue> {
         let mut env = Environment::new_enclosed(&self.closure);
-        for (param, value) in self.decl.params.iter().zip(args) {
-            env.define(param.clone(), value.clone());
+        for (i, (param, value)) in self.decl.params.iter().zip(args).enumerate() {
+            // Parameters always live in a scope the resolver assigned slots to, but fall back to
+            // the parameter's position (parameters are declared first, so parameter `i` is slot
+            // `i`) if that's somehow not the case, rather than panicking.
+            match interpreter.decl_slot(param.id) {
+                Some(slot) => env.define_at_slot(slot, value.clone()),
+                None => env.define_at_slot(i, value.clone()),
+            }
         }
         let real_returned_value = match interpreter.eval_block(&self.decl.body, env) {
             Ok(()) => LoxValue::Nil,
@@ -160,7 +186,7 @@ impl LoxCallable for LoxFunction {
         //
         // Note that if an error arises from the initializer it is not ignored.
         if self.is_class_init {
-            Ok(self.closure.read_at(0, "this"))
+            Ok(self.closure.read_at(0, 0))
         } else {
             Ok(real_returned_value)
         }
@@ -169,6 +195,10 @@ impl LoxCallable for LoxFunction {
     fn arity(&self) -> usize {
         self.decl.params.len()
     }
+
+    fn is_getter(&self) -> bool {
+        self.decl.kind == MethodKind::Getter
+    }
 }
 
 impl Display for LoxFunction {
@@ -212,12 +242,31 @@ impl Debug for NativeFunction {
 #[derive(Debug, Clone)]
 pub struct LoxClass {
     pub name: LoxIdent,
-    pub methods: HashMap<String, Rc<LoxFunction>>,
+    pub methods: HashMap<Symbol, Rc<LoxFunction>>,
+    /// Methods declared with a leading `class` keyword. Dispatched on the class value itself (see
+    /// `Interpreter::eval_get_expr`), never bound to an instance's `this`.
+    pub statics: HashMap<Symbol, Rc<LoxFunction>>,
+    pub superclass: Option<Rc<LoxClass>>,
 }
 
 impl LoxClass {
-    pub fn get_method(&self, ident: impl AsRef<str>) -> Option<Rc<LoxFunction>> {
-        self.methods.get(ident.as_ref()).cloned()
+    /// Looks up a method, falling back to the superclass chain if it isn't defined directly on
+    /// this class.
+    pub fn get_method(&self, name: Symbol) -> Option<Rc<LoxFunction>> {
+        self.methods.get(&name).cloned().or_else(|| {
+            self.superclass
+                .as_ref()
+                .and_then(|superclass| superclass.get_method(name))
+        })
+    }
+
+    /// Looks up a static method, falling back to the superclass chain.
+    pub fn get_static_method(&self, name: Symbol) -> Option<Rc<LoxFunction>> {
+        self.statics.get(&name).cloned().or_else(|| {
+            self.superclass
+                .as_ref()
+                .and_then(|superclass| superclass.get_static_method(name))
+        })
     }
 }
 
@@ -233,20 +282,27 @@ impl LoxCallable for LoxClass {
             properties: RefCell::new(HashMap::new()),
         });
         // Run the class' initializer if it's defined.
-        if let Some(init) = instance.get_bound_method("init") {
+        if let Some(init) = instance.get_bound_method(INIT_METHOD.into()) {
             init.call(interpreter, args)?;
         }
         Ok(LoxValue::Object(instance))
     }
 
     fn arity(&self) -> usize {
-        match self.get_method("init") {
+        match self.get_method(INIT_METHOD.into()) {
             Some(function) => function.arity(),
             None => 0,
         }
     }
+
+    fn as_class(&self) -> Option<&LoxClass> {
+        Some(self)
+    }
 }
 
+/// The name Lox gives a class' initializer method.
+const INIT_METHOD: &str = "init";
+
 impl Display for LoxClass {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "<class {}>", self.name)
@@ -256,7 +312,7 @@ impl Display for LoxClass {
 #[derive(Debug, Clone)]
 pub struct LoxInstance {
     pub constructor: Rc<LoxClass>,
-    properties: RefCell<HashMap<String, LoxValue>>,
+    properties: RefCell<HashMap<Symbol, LoxValue>>,
 }
 
 impl LoxInstance {
@@ -265,7 +321,7 @@ impl LoxInstance {
             return Ok(value.clone());
         }
 
-        if let Some(method) = self.get_bound_method(ident) {
+        if let Some(method) = self.get_bound_method(ident.name) {
             return Ok(LoxValue::Function(method));
         }
 
@@ -275,14 +331,12 @@ impl LoxInstance {
     }
 
     pub fn set(&self, ident: &LoxIdent, value: LoxValue) {
-        self.properties
-            .borrow_mut()
-            .insert(ident.name.clone(), value);
+        self.properties.borrow_mut().insert(ident.name, value);
     }
 
-    pub fn get_bound_method(self: &Rc<Self>, ident: impl AsRef<str>) -> Option<Rc<LoxFunction>> {
+    pub fn get_bound_method(self: &Rc<Self>, name: Symbol) -> Option<Rc<LoxFunction>> {
         self.constructor
-            .get_method(ident)
+            .get_method(name)
             .map(|unbound| unbound.bind(self))
     }
 }
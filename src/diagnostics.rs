@@ -1,41 +1,219 @@
-#[derive(Debug)]
+use std::io::{IsTerminal, Write};
+
+use crate::span::{LineMap, Span};
+
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    fn as_str(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+
+    fn color(self) -> &'static str {
+        match self {
+            Severity::Error => RED,
+            Severity::Warning => YELLOW,
+        }
+    }
+}
+
+/// A secondary span annotated with an explanatory message, rendered as its own underlined
+/// window below the primary one (e.g. pointing at a superclass name while the primary span
+/// points at the class name).
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+impl Label {
+    pub fn new(span: Span, message: impl Into<String>) -> Self {
+        Label {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+/// A diagnosable error or warning: a primary span/message plus any number of secondary labeled
+/// spans giving extra context.
+#[derive(Debug, Clone)]
 pub struct Diagnostic {
-    line: usize,
-    message: String,
+    pub severity: Severity,
+    pub message: String,
+    pub primary: Span,
+    pub labels: Vec<Label>,
 }
 
 impl Diagnostic {
-    pub fn report(&self) {
-        eprintln!("[line {}] Error: {}", self.line, self.message);
+    pub fn error(primary: Span, message: impl Into<String>) -> Self {
+        Diagnostic {
+            severity: Severity::Error,
+            message: message.into(),
+            primary,
+            labels: Vec::new(),
+        }
+    }
+
+    pub fn warning(primary: Span, message: impl Into<String>) -> Self {
+        Diagnostic {
+            severity: Severity::Warning,
+            message: message.into(),
+            primary,
+            labels: Vec::new(),
+        }
+    }
+
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
     }
 }
 
-#[derive(Debug, Default)]
-pub struct Diagnostics {
-    diagnostics: Vec<Diagnostic>,
+/// Renders every diagnostic as an annotated source snippet, sorted by primary span position so
+/// errors read top-to-bottom the way they appear in the file.
+pub fn render_all(writer: &mut dyn Write, source: &str, diagnostics: &mut [Diagnostic]) {
+    diagnostics.sort_by_key(|d| d.primary.lo);
+    for diagnostic in diagnostics.iter() {
+        render(writer, source, diagnostic);
+    }
 }
 
-impl Diagnostics {
-    /// Creates a new diagnostic bag.
-    pub fn new() -> Self {
-        Self::default()
+/// Renders a single diagnostic, e.g.:
+///
+/// ```text
+/// error: A class can't inherit from itself
+///   --> 3:14
+///   3 | class Oops < Oops {
+///                      ^^^^
+///   3 | class Oops < Oops {
+///               ---- class name
+/// ```
+///
+/// The primary window comes first and carries no message of its own; each label then gets a
+/// window of its own, underlined with `-` and followed by the label's message.
+///
+/// When stderr is a terminal, the severity label and carets are additionally wrapped in ANSI
+/// color codes; piped or redirected output (where `is_terminal()` is false) stays plain text.
+pub fn render(writer: &mut dyn Write, source: &str, diagnostic: &Diagnostic) {
+    let color = std::io::stderr().is_terminal();
+
+    if color {
+        writeln!(
+            writer,
+            "{}{}{}: {}{}{}",
+            diagnostic.severity.color(),
+            diagnostic.severity.as_str(),
+            RESET,
+            BOLD,
+            diagnostic.message,
+            RESET
+        )
+        .unwrap();
+    } else {
+        writeln!(
+            writer,
+            "{}: {}",
+            diagnostic.severity.as_str(),
+            diagnostic.message
+        )
+        .unwrap();
     }
 
-    /// Creates a new diagnostic.
-    pub fn diagnose(&mut self, line: usize, message: impl Into<String>) {
-        let message = message.into();
-        self.diagnostics.push(Diagnostic { line, message });
+    let position = LineMap::new(source).locate(diagnostic.primary.lo);
+    writeln!(writer, "  --> {}", position).unwrap();
+
+    render_window(writer, source, diagnostic.primary, None, '^', diagnostic.severity, color);
+    for label in &diagnostic.labels {
+        render_window(writer, source, label.span, Some(&label.message), '-', diagnostic.severity, color);
     }
+    writeln!(writer).unwrap();
+}
 
-    /// Checks if there are no diagnostics.
-    pub fn is_empty(&self) -> bool {
-        self.diagnostics.is_empty()
+/// Renders every source line a span touches, each followed by a caret/underline row spanning
+/// exactly the offending columns on that line, attaching `message` (if any) to the last one.
+/// The underline (and its label, if any) are colored to match `severity` when `color` is set;
+/// callers derive `color` from whether stderr is actually a terminal, so piping/redirecting
+/// output drops back to plain text.
+fn render_window(
+    writer: &mut dyn Write,
+    source: &str,
+    span: Span,
+    message: Option<&str>,
+    marker: char,
+    severity: Severity,
+    color: bool,
+) {
+    let line_map = LineMap::new(source);
+    let sections = line_sections(source, &line_map, span);
+    let gutter_width = sections.last().map(|s| s.line).unwrap_or(0).to_string().len().max(3);
+
+    for (i, section) in sections.iter().enumerate() {
+        let gutter = format!("{:>width$} | ", section.line, width = gutter_width);
+        writeln!(writer, "{}{}", gutter, &source[section.line_start..section.line_end]).unwrap();
+
+        let underline: String = std::iter::repeat(marker)
+            .take((section.col_end - section.col_start).max(1))
+            .collect();
+        let underline = if color {
+            format!("{}{}{}", severity.color(), underline, RESET)
+        } else {
+            underline
+        };
+        let pad = " ".repeat(gutter.len() + section.col_start);
+        if i == sections.len() - 1 {
+            match message {
+                Some(message) => writeln!(writer, "{}{} {}", pad, underline, message).unwrap(),
+                None => writeln!(writer, "{}{}", pad, underline).unwrap(),
+            }
+        } else {
+            writeln!(writer, "{}{}", pad, underline).unwrap();
+        }
     }
+}
+
+/// One line of source a `Span` passes through, with the byte bounds of the line itself and the
+/// columns (relative to the line start) that the span covers on that line specifically.
+struct LineSection {
+    line: usize,
+    line_start: usize,
+    line_end: usize,
+    col_start: usize,
+    col_end: usize,
+}
+
+/// Splits `span` into one `LineSection` per source line it crosses.
+fn line_sections(source: &str, line_map: &LineMap, span: Span) -> Vec<LineSection> {
+    let mut sections = Vec::new();
+    let mut pos = span.lo;
+    loop {
+        let line_start = source[..pos].rfind('\n').map(|p| p + 1).unwrap_or(0);
+        let line_end = source[pos..].find('\n').map(|p| p + pos).unwrap_or(source.len());
+
+        sections.push(LineSection {
+            line: line_map.locate(pos).line,
+            line_start,
+            line_end,
+            col_start: pos - line_start,
+            col_end: span.hi.min(line_end) - line_start,
+        });
 
-    /// Reports all diagnostics.
-    pub fn report_all(&self) {
-        for diagnostic in &self.diagnostics {
-            diagnostic.report();
+        if span.hi <= line_end || line_end >= source.len() {
+            break;
         }
+        pos = line_end + 1; // Skip the `\n`.
     }
+    sections
 }
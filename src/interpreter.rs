@@ -6,19 +6,29 @@ use crate::{
         stmt::{self, Stmt, StmtKind},
         AstId,
     },
-    data::{LoxClass, LoxFunction, LoxIdent, LoxInstance, LoxValue, NativeFunction},
+    data::{LoxClass, LoxFunction, LoxIdent, LoxInstance, LoxValue},
     interpreter::{control_flow::ControlFlow, environment::Environment, error::RuntimeError},
     span::Span,
+    symbol::Symbol,
     token::TokenKind,
 };
 
+pub mod builtins;
 pub mod control_flow;
 pub mod environment;
 pub mod error;
 
 #[derive(Debug)]
 pub struct Interpreter {
-    locals: HashMap<AstId, usize>,
+    /// Maps a binding *use*'s `AstId` (a `Var`/`Assignment`/`This`/`Super` node) to the `(depth,
+    /// slot)` pair the resolver computed for it: how many enclosing scopes to walk, and which slot
+    /// in that scope. Absent entries are unresolved, i.e. genuine globals.
+    locals: HashMap<AstId, (usize, usize)>,
+    /// Maps a binding *declaration*'s `AstId` (a `var`/`fun`/`class` name, or a parameter) to the
+    /// slot the resolver assigned it in its own scope, if it resolved to a local scope at all.
+    /// Consulted by `define_binding` so defining a local writes to that slot instead of the
+    /// name-keyed global table.
+    decl_slots: HashMap<AstId, usize>,
     globals: Environment,
     env: Environment,
 }
@@ -32,6 +42,16 @@ impl Interpreter {
             Ok(()) => Ok(()),
             Err(ControlFlow::Err(err)) => Err(err),
             Err(ControlFlow::Return(_)) => unreachable!(),
+            // Reachable only if `stmts` was interpreted without having gone through the `Resolver`
+            // first, which statically rejects a `break`/`continue` outside of a loop.
+            Err(ControlFlow::Break(span)) => Err(RuntimeError::IllegalLoopControl {
+                keyword: "break",
+                span,
+            }),
+            Err(ControlFlow::Continue(span)) => Err(RuntimeError::IllegalLoopControl {
+                keyword: "continue",
+                span,
+            }),
         }
     }
 
@@ -55,6 +75,8 @@ impl Interpreter {
             If(if_stmt) => self.eval_if_stmt(if_stmt),
             While(while_stmt) => self.eval_while_stmt(while_stmt),
             Return(return_stmt) => self.eval_return_stmt(return_stmt),
+            Break(break_stmt) => Err(ControlFlow::Break(break_stmt.break_span)),
+            Continue(continue_stmt) => Err(ControlFlow::Continue(continue_stmt.continue_span)),
             Print(print) => self.eval_print_stmt(print),
             Block(block) => self.eval_block(&block.stmts, Environment::new_enclosed(&self.env)),
             Expr(expr) => self.eval_expr(&expr.expr).map(drop),
@@ -67,39 +89,80 @@ impl Interpreter {
             Some(expr) => self.eval_expr(expr)?,
             None => LoxValue::Nil,
         };
-        self.env.define(var.name.clone(), value);
+        self.define_binding(&var.name, value);
         Ok(())
     }
 
     fn eval_class_stmt(&mut self, class: &stmt::ClassDecl) -> CFResult<()> {
-        let methods = class
-            .methods
-            .iter()
-            .cloned()
-            .map(|decl| {
-                (
-                    decl.name.name.clone(),
-                    Rc::new(LoxFunction {
-                        is_class_init: decl.name.name == "init",
-                        decl: Rc::new(decl),
-                        closure: self.env.clone(),
-                    }),
-                )
-            })
-            .collect();
-        self.env.define(
-            class.name.clone(),
+        let superclass = class
+            .super_name
+            .as_ref()
+            .map(|super_name| self.eval_superclass(super_name))
+            .transpose()?;
+
+        // Methods close over an extra scope binding `super` to the superclass, when there is one,
+        // so `super.method()` calls inside a method body can find it (mirroring how `this` is
+        // bound by `LoxFunction::bind` when the method is actually called).
+        let methods_env = match &superclass {
+            Some(superclass) => {
+                let mut env = Environment::new_enclosed(&self.env);
+                // `super` is always the sole (hence slot 0) binding of this scope, mirroring the
+                // resolver's `initialize("super")`.
+                env.define_at_slot(0, LoxValue::Function(superclass.clone()));
+                env
+            }
+            None => self.env.clone(),
+        };
+
+        let mut methods = HashMap::new();
+        let mut statics = HashMap::new();
+        for decl in class.methods.iter().cloned() {
+            let name = decl.name.name;
+            let function = Rc::new(LoxFunction {
+                is_class_init: decl.name.as_ref() == "init",
+                closure: methods_env.clone(),
+                decl: Rc::new(decl.clone()),
+            });
+            match decl.kind {
+                stmt::MethodKind::Static => statics.insert(name, function),
+                stmt::MethodKind::Function | stmt::MethodKind::Getter => {
+                    methods.insert(name, function)
+                }
+            };
+        }
+        self.define_binding(
+            &class.name,
             LoxValue::Function(Rc::new(LoxClass {
                 name: class.name.clone(),
                 methods,
+                statics,
+                superclass,
             })),
         );
         Ok(())
     }
 
+    /// Evaluates a class' `super_name`, ensuring it names a class (and not, say, a plain
+    /// function), the way `ensure_object` does for property access on a non-object.
+    fn eval_superclass(&mut self, super_name: &LoxIdent) -> CFResult<Rc<LoxClass>> {
+        let value = self.lookup_variable(super_name)?;
+        let class = match &value {
+            LoxValue::Function(callable) => callable.as_class(),
+            _ => None,
+        };
+        match class {
+            Some(class) => Ok(Rc::new(class.clone())),
+            None => Err(RuntimeError::UnsupportedType {
+                message: "Superclass must be a class".into(),
+                span: super_name.span,
+            }
+            .into()),
+        }
+    }
+
     fn eval_fun_stmt(&mut self, fun: &stmt::FunDecl) -> CFResult<()> {
-        self.env.define(
-            fun.name.clone(),
+        self.define_binding(
+            &fun.name,
             LoxValue::Function(Rc::new(LoxFunction {
                 decl: Rc::new(fun.clone()),
                 closure: self.env.clone(),
@@ -121,7 +184,12 @@ impl Interpreter {
 
     fn eval_while_stmt(&mut self, while_stmt: &stmt::While) -> CFResult<()> {
         while lox_is_truthy(&self.eval_expr(&while_stmt.cond)?) {
-            self.eval_stmt(&while_stmt.body)?;
+            match self.eval_stmt(&while_stmt.body) {
+                Ok(()) => {}
+                Err(ControlFlow::Break(_)) => break,
+                Err(ControlFlow::Continue(_)) => continue,
+                Err(other) => return Err(other),
+            }
         }
         Ok(())
     }
@@ -161,6 +229,7 @@ impl Interpreter {
         match &expr.kind {
             Lit(lit) => self.eval_lit_expr(lit),
             This(this) => self.lookup_variable(&this.name),
+            Super(sup) => self.eval_super_expr(sup),
             Var(var) => self.lookup_variable(&var.name),
             Group(group) => self.eval_group_expr(group),
             Get(get) => self.eval_get_expr(get),
@@ -170,6 +239,10 @@ impl Interpreter {
             Binary(binary) => self.eval_binary_expr(binary),
             Logical(logical) => self.eval_logical_expr(logical),
             Assignment(assignment) => self.eval_assignment_expr(assignment),
+            // Stands in for an expression the parser already failed (and diagnosed) on; the
+            // interpreter never runs over a tree containing one of these (see the `Dummy` arm in
+            // `eval_stmt`).
+            Error(_) => unreachable!(),
         }
     }
 
@@ -183,8 +256,28 @@ impl Interpreter {
 
     fn eval_get_expr(&mut self, get: &expr::Get) -> CFResult<LoxValue> {
         let maybe_object = self.eval_expr(&get.object)?;
+
+        // A property access on the class value itself (rather than an instance of it) dispatches
+        // to a static method instead of going through `LoxInstance::get`.
+        if let LoxValue::Function(callable) = &maybe_object {
+            if let Some(class) = callable.as_class() {
+                if let Some(method) = class.get_static_method(get.name.name) {
+                    return Ok(LoxValue::Function(method));
+                }
+            }
+        }
+
         let instance = Self::ensure_object(maybe_object, get.name.span)?;
-        Ok(instance.get(&get.name)?)
+        let value = instance.get(&get.name)?;
+
+        // A getter is invoked immediately on access, rather than returned as a bound method.
+        if let LoxValue::Function(callable) = &value {
+            if callable.is_getter() {
+                return callable.clone().call(self, &[]);
+            }
+        }
+
+        Ok(value)
     }
 
     fn eval_set_expr(&mut self, set: &expr::Set) -> CFResult<LoxValue> {
@@ -195,23 +288,78 @@ impl Interpreter {
         Ok(value)
     }
 
+    /// Resolves a `super.method` expression. The resolver guarantees `super` was bound to a class
+    /// exactly one scope further out than `this`, so the bound instance is read one scope closer
+    /// than the resolved `super` distance, mirroring the `getAt`/`getAt(distance - 1)` pairing
+    /// from the reference jlox implementation.
+    fn eval_super_expr(&mut self, sup: &expr::Super) -> CFResult<LoxValue> {
+        let &(distance, slot) = self
+            .locals
+            .get(&sup.super_ident.id)
+            .expect("the resolver always resolves `super` expressions to an enclosing scope");
+
+        let superclass_value = self.env.read_at(distance, slot);
+        let method = match &superclass_value {
+            LoxValue::Function(callable) => callable
+                .as_class()
+                .expect("the resolver only ever binds `super` to a class")
+                .get_method(sup.method.name),
+            _ => unreachable!("the resolver only ever binds `super` to a class"),
+        };
+
+        // `this` is always the sole (hence slot 0) binding of the scope directly enclosing
+        // `super`'s, mirroring how the resolver's `initialize` sets both of them up.
+        let instance = match self.env.read_at(distance - 1, 0) {
+            LoxValue::Object(instance) => instance,
+            _ => unreachable!("the \"this\" scope always directly encloses the \"super\" scope"),
+        };
+
+        match method {
+            Some(method) => {
+                let value = LoxValue::Function(method.bind(&instance));
+
+                // Mirrors `eval_get_expr`: a superclass getter is invoked immediately on access,
+                // rather than returned as a bound method.
+                if let LoxValue::Function(callable) = &value {
+                    if callable.is_getter() {
+                        return callable.clone().call(self, &[]);
+                    }
+                }
+
+                Ok(value)
+            }
+            None => Err(RuntimeError::UndefinedProperty {
+                ident: sup.method.clone(),
+            }
+            .into()),
+        }
+    }
+
     fn eval_call_expr(&mut self, call: &expr::Call, span: Span) -> CFResult<LoxValue> {
-        use LoxValue::*;
         let callee = self.eval_expr(&call.callee)?;
         let args = call
             .args
             .iter()
             .map(|expr| self.eval_expr(expr))
             .collect::<Result<Vec<_>, _>>()?;
+        self.dispatch_call(callee, &args, span)
+    }
 
+    /// Checks that `callee` is callable with exactly `args.len()` arguments and invokes it.
+    /// Shared by `eval_call_expr` and `eval_pipe_expr`, which both end up dispatching a call once
+    /// they've each assembled their own callee/argument list.
+    fn dispatch_call(
+        &mut self,
+        callee: LoxValue,
+        args: &[LoxValue],
+        span: Span,
+    ) -> CFResult<LoxValue> {
+        use LoxValue::*;
         match callee {
-            Function(callable) if callable.arity() == args.len() => callable.call(self, &args),
-            Function(callable) => Err(RuntimeError::UnsupportedType {
-                message: format!(
-                    "Expected {} arguments, but got {}",
-                    callable.arity(),
-                    args.len()
-                ),
+            Function(callable) if callable.arity() == args.len() => callable.call(self, args),
+            Function(callable) => Err(RuntimeError::ArityMismatch {
+                expected: callable.arity(),
+                got: args.len(),
                 span,
             }
             .into()),
@@ -249,6 +397,14 @@ impl Interpreter {
 
     fn eval_binary_expr(&mut self, binary: &expr::Binary) -> CFResult<LoxValue> {
         use LoxValue::*;
+
+        // `|>` doesn't evaluate its right-hand side as a plain expression like every other binary
+        // operator does (see `eval_pipe_expr`), so it's special-cased before the eager `left`/
+        // `right` evaluation the rest of this function relies on.
+        if let TokenKind::Pipe = &binary.operator.kind {
+            return self.eval_pipe_expr(binary);
+        }
+
         let left = self.eval_expr(&binary.left)?;
         let right = self.eval_expr(&binary.right)?;
         match &binary.operator.kind {
@@ -289,10 +445,36 @@ impl Interpreter {
             TokenKind::Less => bin_comparison_operator!(left < right, binary.operator),
             TokenKind::LessEqual => bin_comparison_operator!(left <= right, binary.operator),
 
+            TokenKind::Pipe => unreachable!("handled above, before evaluating `left`/`right`"),
+
             unexpected => unreachable!("Invalid binary operator ({:?}).", unexpected),
         }
     }
 
+    /// Evaluates `lhs |> rhs` by invoking `rhs` with `lhs` as an argument, reusing
+    /// `dispatch_call`'s callable/arity checks. When `rhs` is itself a call expression
+    /// (`x |> f(a, b)`), this desugars to `f(x, a, b)` by prepending the piped value to the call's
+    /// existing argument list, rather than evaluating `f(a, b)` first and piping its result in as
+    /// a single argument.
+    fn eval_pipe_expr(&mut self, binary: &expr::Binary) -> CFResult<LoxValue> {
+        let piped = self.eval_expr(&binary.left)?;
+        match &binary.right.kind {
+            ExprKind::Call(call) => {
+                let callee = self.eval_expr(&call.callee)?;
+                let mut args = Vec::with_capacity(call.args.len() + 1);
+                args.push(piped);
+                for arg in &call.args {
+                    args.push(self.eval_expr(arg)?);
+                }
+                self.dispatch_call(callee, &args, binary.right.span)
+            }
+            _ => {
+                let callee = self.eval_expr(&binary.right)?;
+                self.dispatch_call(callee, &[piped], binary.operator.span)
+            }
+        }
+    }
+
     fn eval_logical_expr(&mut self, logical: &expr::Logical) -> CFResult<LoxValue> {
         let left = self.eval_expr(&logical.left)?;
         match &logical.operator.kind {
@@ -304,8 +486,8 @@ impl Interpreter {
 
     fn eval_assignment_expr(&mut self, assignment: &expr::Assignment) -> CFResult<LoxValue> {
         let value = self.eval_expr(&assignment.value)?;
-        if let Some(distance) = self.locals.get(&assignment.name.id) {
-            Ok(self.env.assign_at(*distance, &assignment.name, value))
+        if let Some(&(distance, slot)) = self.locals.get(&assignment.name.id) {
+            Ok(self.env.assign_at(distance, slot, value))
         } else {
             Ok(self.globals.assign(&assignment.name, value)?)
         }
@@ -315,31 +497,60 @@ impl Interpreter {
 impl Interpreter {
     pub fn new() -> Self {
         let mut globals = Environment::new();
-
-        def_native!(
-            globals.clock / 0,
-            fn clock(_: &[LoxValue]) -> CFResult<LoxValue> {
-                use std::time::{SystemTime, UNIX_EPOCH};
-                let start = SystemTime::now();
-                let since_the_epoch = start.duration_since(UNIX_EPOCH).unwrap().as_secs_f64();
-                Ok(LoxValue::Number(since_the_epoch))
-            }
-        );
+        builtins::install(&mut globals);
 
         Self {
             env: globals.clone(),
             globals,
             locals: HashMap::new(),
+            decl_slots: HashMap::new(),
+        }
+    }
+
+    pub fn resolve_local(&mut self, ident: &LoxIdent, depth: usize, slot: usize) {
+        self.locals.insert(ident.id, (depth, slot));
+    }
+
+    /// Records the slot `declare`/`declare_param` assigned a binding's declaration in its own
+    /// scope. Unlike `resolve_local` (which maps a *use*), this maps the declaration itself, so
+    /// `define_binding` can find it again when the binding's initializer actually runs.
+    pub fn record_slot(&mut self, id: AstId, slot: usize) {
+        self.decl_slots.insert(id, slot);
+    }
+
+    /// Defines `ident` in the current scope: at the slot the resolver assigned it, if its
+    /// declaration resolved to a local scope, or by name in the global table otherwise (globals
+    /// can be declared dynamically, so the resolver leaves them unresolved).
+    fn define_binding(&mut self, ident: &LoxIdent, value: LoxValue) {
+        match self.decl_slots.get(&ident.id) {
+            Some(&slot) => self.env.define_at_slot(slot, value),
+            None => self.env.define(ident.clone(), value),
         }
     }
 
-    pub fn resolve_local(&mut self, ident: &LoxIdent, depth: usize) {
-        self.locals.insert(ident.id, depth);
+    /// Like `define_binding`, but for a slot lookup made from outside `Interpreter` (namely
+    /// `LoxFunction::call` binding its parameters into a fresh call scope).
+    pub(crate) fn decl_slot(&self, id: AstId) -> Option<usize> {
+        self.decl_slots.get(&id).copied()
+    }
+
+    /// Lists every name pre-registered in the global scope (currently just the native-function
+    /// standard library). Passed to `Resolver::new` so it can tell a genuinely undefined global
+    /// apart from a builtin.
+    pub fn global_names(&self) -> Vec<Symbol> {
+        self.globals.names()
+    }
+
+    /// Looks up the scope distance the `Resolver` computed for `id`, if any (a binding with none
+    /// resolves as a global at runtime). Used by `ast::dbg::print_resolved_tree` to annotate the
+    /// printed tree with the resolution each binding actually got.
+    pub fn local_depth(&self, id: AstId) -> Option<usize> {
+        self.locals.get(&id).map(|&(depth, _)| depth)
     }
 
     fn lookup_variable(&self, ident: &LoxIdent) -> CFResult<LoxValue> {
-        if let Some(distance) = self.locals.get(&ident.id) {
-            Ok(self.env.read_at(*distance, ident))
+        if let Some(&(distance, slot)) = self.locals.get(&ident.id) {
+            Ok(self.env.read_at(distance, slot))
         } else {
             Ok(self.globals.read(ident)?)
         }
@@ -385,6 +596,10 @@ fn lox_is_equal(a: &LoxValue, b: &LoxValue) -> bool {
         (Object(a), Object(b)) => Rc::ptr_eq(a, b),
         (Boolean(a), Boolean(b)) => a == b,
         (Number(a), Number(b)) => a == b,
+        // A plain content compare: the global interner is scoped to compile-time-derived
+        // identifiers and literals (see `src/symbol.rs`), which never get evicted, so routing
+        // arbitrary runtime string equality through it would leak every distinct string a program
+        // ever compares (e.g. in a loop building strings from numbers) for the rest of the run.
         (String(a), String(b)) => a == b,
         (Nil, Nil) => true,
         // This is not exhaustive, pay close attention if a new `LoxValue` variant is introduced.
@@ -432,20 +647,3 @@ macro_rules! bin_comparison_operator {
     };
 }
 use bin_comparison_operator;
-
-macro_rules! def_native {
-    ($globals:ident . $name:ident / $arity:expr  , $fn:item) => {
-        $fn
-        let id = AstId::new();
-        let name: &'static str = stringify!($name);
-        $globals.define(
-            LoxIdent { name: name.into(), span: Span::new(0, 0), id },
-            LoxValue::Function(Rc::new(NativeFunction {
-                name,
-                fn_ptr: $name,
-                arity: $arity
-            })),
-        );
-    };
-}
-use def_native;
@@ -0,0 +1,250 @@
+use std::{
+    io::{self, BufRead, Write},
+    rc::Rc,
+};
+
+use crate::{
+    ast::AstId,
+    data::{LoxIdent, LoxValue, NativeFunction},
+    interpreter::{environment::Environment, error::RuntimeError, CFResult},
+    span::Span,
+};
+
+/// Installs the native-function standard library into `globals`. Called once when a fresh
+/// `Interpreter` is constructed, before any user code runs.
+pub fn install(globals: &mut Environment) {
+    def_native!(
+        globals.clock / 0,
+        fn clock(_: &[LoxValue]) -> CFResult<LoxValue> {
+            use std::time::{SystemTime, UNIX_EPOCH};
+            let start = SystemTime::now();
+            let since_the_epoch = start.duration_since(UNIX_EPOCH).unwrap().as_secs_f64();
+            Ok(LoxValue::Number(since_the_epoch))
+        }
+    );
+
+    def_native!(
+        globals.str / 1,
+        fn str(args: &[LoxValue]) -> CFResult<LoxValue> {
+            Ok(LoxValue::String(args[0].to_string()))
+        }
+    );
+
+    def_native!(
+        globals.len / 1,
+        fn len(args: &[LoxValue]) -> CFResult<LoxValue> {
+            match &args[0] {
+                LoxValue::String(s) => Ok(LoxValue::Number(s.chars().count() as f64)),
+                other => Err(RuntimeError::UnsupportedType {
+                    message: format!("`len` expects a string, got `{}`", other.type_name()),
+                    span: Span::new(0, 0),
+                }
+                .into()),
+            }
+        }
+    );
+
+    def_native!(
+        globals.num / 1,
+        fn num(args: &[LoxValue]) -> CFResult<LoxValue> {
+            match &args[0] {
+                LoxValue::String(s) => s.trim().parse().map(LoxValue::Number).map_err(|_| {
+                    RuntimeError::UnsupportedType {
+                        message: format!("`num` can't parse `{}` as a number", s),
+                        span: Span::new(0, 0),
+                    }
+                    .into()
+                }),
+                other => Err(RuntimeError::UnsupportedType {
+                    message: format!("`num` expects a string, got `{}`", other.type_name()),
+                    span: Span::new(0, 0),
+                }
+                .into()),
+            }
+        }
+    );
+
+    def_native!(
+        globals.sqrt / 1,
+        fn sqrt(args: &[LoxValue]) -> CFResult<LoxValue> {
+            match &args[0] {
+                LoxValue::Number(n) => Ok(LoxValue::Number(n.sqrt())),
+                other => Err(RuntimeError::UnsupportedType {
+                    message: format!("`sqrt` expects a number, got `{}`", other.type_name()),
+                    span: Span::new(0, 0),
+                }
+                .into()),
+            }
+        }
+    );
+
+    def_native!(
+        globals.floor / 1,
+        fn floor(args: &[LoxValue]) -> CFResult<LoxValue> {
+            match &args[0] {
+                LoxValue::Number(n) => Ok(LoxValue::Number(n.floor())),
+                other => Err(RuntimeError::UnsupportedType {
+                    message: format!("`floor` expects a number, got `{}`", other.type_name()),
+                    span: Span::new(0, 0),
+                }
+                .into()),
+            }
+        }
+    );
+
+    def_native!(
+        globals.pow / 2,
+        fn pow(args: &[LoxValue]) -> CFResult<LoxValue> {
+            match (&args[0], &args[1]) {
+                (LoxValue::Number(base), LoxValue::Number(exponent)) => {
+                    Ok(LoxValue::Number(base.powf(*exponent)))
+                }
+                (a, b) => Err(RuntimeError::UnsupportedType {
+                    message: format!(
+                        "`pow` expects two numbers, got `{}` and `{}`",
+                        a.type_name(),
+                        b.type_name()
+                    ),
+                    span: Span::new(0, 0),
+                }
+                .into()),
+            }
+        }
+    );
+
+    def_native!(
+        globals.substr / 3,
+        fn substr(args: &[LoxValue]) -> CFResult<LoxValue> {
+            match (&args[0], &args[1], &args[2]) {
+                (LoxValue::String(s), LoxValue::Number(start), LoxValue::Number(len)) => {
+                    let chars: Vec<char> = s.chars().collect();
+                    let start = (*start as usize).min(chars.len());
+                    let end = (start + *len as usize).min(chars.len());
+                    Ok(LoxValue::String(chars[start..end].iter().collect()))
+                }
+                _ => Err(RuntimeError::UnsupportedType {
+                    message: "`substr` expects a string and two numbers (start, length)".into(),
+                    span: Span::new(0, 0),
+                }
+                .into()),
+            }
+        }
+    );
+
+    def_native!(
+        globals.chr / 1,
+        fn chr(args: &[LoxValue]) -> CFResult<LoxValue> {
+            match &args[0] {
+                LoxValue::Number(n) => match char::from_u32(*n as u32) {
+                    Some(c) => Ok(LoxValue::String(c.to_string())),
+                    None => Err(RuntimeError::UnsupportedType {
+                        message: format!("`chr` got an invalid code point `{}`", n),
+                        span: Span::new(0, 0),
+                    }
+                    .into()),
+                },
+                other => Err(RuntimeError::UnsupportedType {
+                    message: format!("`chr` expects a number, got `{}`", other.type_name()),
+                    span: Span::new(0, 0),
+                }
+                .into()),
+            }
+        }
+    );
+
+    def_native!(
+        globals.ord / 1,
+        fn ord(args: &[LoxValue]) -> CFResult<LoxValue> {
+            match &args[0] {
+                LoxValue::String(s) if s.chars().count() == 1 => {
+                    Ok(LoxValue::Number(s.chars().next().unwrap() as u32 as f64))
+                }
+                other => Err(RuntimeError::UnsupportedType {
+                    message: format!(
+                        "`ord` expects a single-character string, got `{}`",
+                        other.type_name()
+                    ),
+                    span: Span::new(0, 0),
+                }
+                .into()),
+            }
+        }
+    );
+
+    def_native!(
+        globals.type_of / 1,
+        fn type_of(args: &[LoxValue]) -> CFResult<LoxValue> {
+            Ok(LoxValue::String(args[0].type_name().to_string()))
+        }
+    );
+
+    def_native!(
+        globals.abs / 1,
+        fn abs(args: &[LoxValue]) -> CFResult<LoxValue> {
+            match &args[0] {
+                LoxValue::Number(n) => Ok(LoxValue::Number(n.abs())),
+                other => Err(RuntimeError::UnsupportedType {
+                    message: format!("`abs` expects a number, got `{}`", other.type_name()),
+                    span: Span::new(0, 0),
+                }
+                .into()),
+            }
+        }
+    );
+
+    def_native!(
+        globals.print / 1,
+        fn print(args: &[LoxValue]) -> CFResult<LoxValue> {
+            print!("{}", args[0]);
+            io::stdout().flush().ok();
+            Ok(LoxValue::Nil)
+        }
+    );
+
+    def_native!(
+        globals.println / 1,
+        fn println(args: &[LoxValue]) -> CFResult<LoxValue> {
+            println!("{}", args[0]);
+            Ok(LoxValue::Nil)
+        }
+    );
+
+    def_native!(
+        globals.input / 0,
+        fn input(_: &[LoxValue]) -> CFResult<LoxValue> {
+            let mut line = String::new();
+            io::stdin().lock().read_line(&mut line).map_err(|err| {
+                RuntimeError::UnsupportedType {
+                    message: format!("`input` failed to read a line from stdin: {}", err),
+                    span: Span::new(0, 0),
+                }
+            })?;
+            if line.ends_with('\n') {
+                line.pop();
+                if line.ends_with('\r') {
+                    line.pop();
+                }
+            }
+            Ok(LoxValue::String(line))
+        }
+    );
+}
+
+/// Declares a single native function and registers it into `$globals` under `$name`, wrapped in
+/// an `Rc<NativeFunction>` carrying its `$arity`.
+macro_rules! def_native {
+    ($globals:ident . $name:ident / $arity:expr  , $fn:item) => {
+        $fn
+        let id = AstId::new();
+        let name: &'static str = stringify!($name);
+        $globals.define(
+            LoxIdent { name: name.into(), span: Span::new(0, 0), id },
+            LoxValue::Function(Rc::new(NativeFunction {
+                name,
+                fn_ptr: $name,
+                arity: $arity
+            })),
+        );
+    };
+}
+use def_native;
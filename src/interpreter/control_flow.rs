@@ -1,7 +1,18 @@
 use std::error::Error;
 
+use crate::span::Span;
+
+/// The non-local exits a statement's evaluation can produce, unified into one type so `eval_stmt`'s
+/// `Result` can propagate any of them the same way up through calling statements, until something
+/// that actually handles the given kind catches and stops it: a function call for `Return`, a loop
+/// for `Break`/`Continue`.
 pub enum ControlFlow<R, E> {
     Return(R),
+    /// Carries the `break`/`continue` keyword's span, so if one somehow reaches `Interpreter::
+    /// interpret` without an enclosing loop having caught it (the `Resolver` is supposed to reject
+    /// that statically), it can still be reported with a useful position.
+    Break(Span),
+    Continue(Span),
     Err(E),
 }
 
@@ -3,12 +3,32 @@ use std::{cell::RefCell, collections::HashMap, rc::Rc};
 use crate::{
     data::{LoxIdent, LoxValue},
     interpreter::error::RuntimeError,
+    symbol::Symbol,
 };
 
+/// How one scope stores its bindings.
+///
+/// The global scope (the one `Environment` with no `enclosing`) stays name-keyed: globals can be
+/// declared dynamically (e.g. the REPL evaluating one statement at a time), so there's no fixed
+/// set of names a resolver could assign slots to ahead of time. Every other scope is slot-indexed:
+/// the `Resolver` assigns each local a monotonically increasing slot when it declares it, so
+/// `read_at`/`assign_at` index straight into a `Vec` instead of hashing the variable's name.
+#[derive(Debug)]
+enum Bindings {
+    Global(HashMap<Symbol, LoxValue>),
+    Local(Vec<LoxValue>),
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Bindings::Global(HashMap::new())
+    }
+}
+
 #[derive(Debug, Default)]
 struct EnvironmentInner {
     enclosing: Option<Environment>,
-    locals: HashMap<String, LoxValue>,
+    bindings: Bindings,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -22,75 +42,116 @@ impl Environment {
         Default::default()
     }
 
-    /// Creates a new `Environment` enclosing the given `Environment`.
+    /// Creates a new, slot-indexed `Environment` enclosing the given `Environment`.
     pub fn new_enclosed(enclosing: &Environment) -> Self {
         Self {
             inner: Rc::new(RefCell::new(EnvironmentInner {
                 enclosing: Some(enclosing.clone()),
-                locals: HashMap::new(),
+                bindings: Bindings::Local(Vec::new()),
             })),
         }
     }
 
-    /// Defines a variable in the innermost scope.
+    /// Defines a variable by name in the innermost scope. Only meaningful on the global scope;
+    /// every other scope is slot-indexed and declares through `define_at_slot` instead.
     pub fn define(&mut self, ident: LoxIdent, value: LoxValue) {
-        self.inner.borrow_mut().locals.insert(ident.name, value);
+        match &mut self.inner.borrow_mut().bindings {
+            Bindings::Global(locals) => {
+                locals.insert(ident.name, value);
+            }
+            Bindings::Local(_) => {
+                unreachable!("`define` was called on a slot-indexed local scope")
+            }
+        }
     }
 
-    /// Assigns a variable.
+    /// Defines a local at `slot` in the innermost scope. The `Resolver` hands out slots in
+    /// increasing order as it declares names in a scope, and the interpreter defines them in that
+    /// same order, so `slot` is always either already-occupied (re-running a loop body, a
+    /// recursive call, ...) or exactly one past the end.
+    pub fn define_at_slot(&mut self, slot: usize, value: LoxValue) {
+        match &mut self.inner.borrow_mut().bindings {
+            Bindings::Local(slots) => {
+                if slot == slots.len() {
+                    slots.push(value);
+                } else {
+                    slots[slot] = value;
+                }
+            }
+            Bindings::Global(_) => {
+                unreachable!("`define_at_slot` was called on the name-keyed global scope")
+            }
+        }
+    }
+
+    /// Assigns a variable by name, walking up the scope chain until it's found.
     pub fn assign(&mut self, ident: &LoxIdent, value: LoxValue) -> Result<LoxValue, RuntimeError> {
         let mut inner = self.inner.borrow_mut();
-        match inner.locals.get_mut(&ident.name) {
-            Some(var) => {
+        if let Bindings::Global(locals) = &mut inner.bindings {
+            if let Some(var) = locals.get_mut(&ident.name) {
                 *var = value.clone();
-                Ok(value)
+                return Ok(value);
             }
-            None => match &mut inner.enclosing {
-                Some(enclosing) => enclosing.assign(ident, value),
-                None => Err(RuntimeError::UndefinedVariable {
-                    ident: ident.clone(),
-                }),
-            },
+        }
+        match &mut inner.enclosing {
+            Some(enclosing) => enclosing.assign(ident, value),
+            None => Err(RuntimeError::UndefinedVariable {
+                ident: ident.clone(),
+            }),
         }
     }
 
-    /// Reads a variable in a distant scope.
-    pub fn assign_at(&mut self, distance: usize, ident: &LoxIdent, value: LoxValue) -> LoxValue {
-        // This should never panic due to the semantic verifications that the resolver performs.
-        *self
-            .ancestor(distance)
-            .inner
-            .borrow_mut()
-            .locals
-            .get_mut(&ident.name)
-            .unwrap() = value.clone();
+    /// Assigns the local at `slot` in the scope `distance` links up the chain.
+    pub fn assign_at(&mut self, distance: usize, slot: usize, value: LoxValue) -> LoxValue {
+        let ancestor = self.ancestor(distance);
+        let mut inner = ancestor.inner.borrow_mut();
+        match &mut inner.bindings {
+            // This should never panic due to the semantic verifications the resolver performs.
+            Bindings::Local(slots) => slots[slot] = value.clone(),
+            Bindings::Global(_) => {
+                unreachable!("`assign_at` was called on the name-keyed global scope")
+            }
+        }
         value
     }
 
-    /// Reads a variable.
+    /// Reads a variable by name, walking up the scope chain until it's found.
     pub fn read(&self, ident: &LoxIdent) -> Result<LoxValue, RuntimeError> {
         let inner = self.inner.borrow();
-        match inner.locals.get(&ident.name) {
-            Some(var) => Ok(var.clone()),
-            None => match &inner.enclosing {
-                Some(enclosing) => enclosing.read(ident),
-                None => Err(RuntimeError::UndefinedVariable {
-                    ident: ident.clone(),
-                }),
-            },
+        if let Bindings::Global(locals) = &inner.bindings {
+            if let Some(var) = locals.get(&ident.name) {
+                return Ok(var.clone());
+            }
+        }
+        match &inner.enclosing {
+            Some(enclosing) => enclosing.read(ident),
+            None => Err(RuntimeError::UndefinedVariable {
+                ident: ident.clone(),
+            }),
         }
     }
 
-    /// Reads a variable in a distant scope.
-    pub fn read_at(&self, distance: usize, ident: impl AsRef<str>) -> LoxValue {
-        // This should never panic due to the semantic verifications that the resolver performs.
-        self.ancestor(distance)
-            .inner
-            .borrow()
-            .locals
-            .get(ident.as_ref())
-            .unwrap()
-            .clone()
+    /// Reads the local at `slot` in the scope `distance` links up the chain.
+    pub fn read_at(&self, distance: usize, slot: usize) -> LoxValue {
+        let ancestor = self.ancestor(distance);
+        let inner = ancestor.inner.borrow();
+        match &inner.bindings {
+            // This should never panic due to the semantic verifications the resolver performs.
+            Bindings::Local(slots) => slots[slot].clone(),
+            Bindings::Global(_) => {
+                unreachable!("`read_at` was called on the name-keyed global scope")
+            }
+        }
+    }
+
+    /// Lists the names bound directly in this scope (not its enclosing ones). Used by the
+    /// `Resolver` to learn which globals are pre-registered (e.g. native functions) before it
+    /// walks the program. Only meaningful on the global scope.
+    pub fn names(&self) -> Vec<Symbol> {
+        match &self.inner.borrow().bindings {
+            Bindings::Global(locals) => locals.keys().copied().collect(),
+            Bindings::Local(_) => Vec::new(),
+        }
     }
 
     fn ancestor(&self, distance: usize) -> Environment {
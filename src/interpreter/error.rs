@@ -3,33 +3,33 @@ use std::{
     fmt::{self, Display},
 };
 
-use crate::span::Span;
+use crate::{data::LoxIdent, diagnostics::Diagnostic, span::Span};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum RuntimeError {
     UnsupportedType { message: String, span: Span },
 
-    UndefinedVariable { name: String, span: Span },
+    UndefinedVariable { ident: LoxIdent },
+
+    UndefinedProperty { ident: LoxIdent },
 
     ZeroDivision { span: Span },
+
+    ArityMismatch {
+        expected: usize,
+        got: usize,
+        span: Span,
+    },
+
+    /// A `break`/`continue` reached `Interpreter::interpret` without an enclosing loop having
+    /// handled it. The `Resolver` rejects this statically, so this should only ever arise if a
+    /// tree is interpreted without having been resolved first.
+    IllegalLoopControl { keyword: &'static str, span: Span },
 }
 
 impl Display for RuntimeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use RuntimeError::*;
-        match self {
-            UnsupportedType { message, span } => {
-                write!(f, "{}; at position {}", message, span)
-            }
-
-            UndefinedVariable { name, span } => {
-                write!(f, "Undefined variable `{}`; at position {}", name, span)
-            }
-
-            ZeroDivision { span } => {
-                write!(f, "Can not divide by zero; at position {}", span)
-            }
-        }
+        write!(f, "{}; at position {}", self.message(), self.primary_span())
     }
 }
 
@@ -39,10 +39,37 @@ impl RuntimeError {
         use RuntimeError::*;
         match self {
             UnsupportedType { span, .. }
-            | UndefinedVariable { span, .. }
-            | ZeroDivision { span } => *span,
+            | ZeroDivision { span }
+            | ArityMismatch { span, .. }
+            | IllegalLoopControl { span, .. } => *span,
+            UndefinedVariable { ident } | UndefinedProperty { ident } => ident.span,
+        }
+    }
+
+    /// Returns the human-readable description of this error, without any position information.
+    /// Used to build a `Diagnostic`, which renders the position as a source excerpt itself, and
+    /// by `Display`, which appends the position as plain text for contexts without one.
+    fn message(&self) -> String {
+        use RuntimeError::*;
+        match self {
+            UnsupportedType { message, .. } => message.clone(),
+            UndefinedVariable { ident } => format!("Undefined variable `{}`", ident),
+            UndefinedProperty { ident } => format!("Undefined property `{}`", ident),
+            ZeroDivision { .. } => "Can not divide by zero".into(),
+            ArityMismatch { expected, got, .. } => {
+                format!("Expected {} arguments, but got {}", expected, got)
+            }
+            IllegalLoopControl { keyword, .. } => {
+                format!("Illegal {} statement, not inside a loop", keyword)
+            }
         }
     }
 }
 
 impl Error for RuntimeError {}
+
+impl From<&RuntimeError> for Diagnostic {
+    fn from(error: &RuntimeError) -> Self {
+        Diagnostic::error(error.primary_span(), error.message())
+    }
+}
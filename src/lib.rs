@@ -1,8 +1,12 @@
 pub mod ast;
 pub mod data;
+pub mod diagnostics;
 pub mod interpreter;
 pub mod parser;
 pub mod resolver;
 pub mod span;
+pub mod symbol;
 pub mod token;
+pub mod typeck;
 pub mod user;
+pub mod vm;
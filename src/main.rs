@@ -1,10 +1,181 @@
-use std::{env, io};
+use std::{env, fs, io, process};
 
-use lox::user;
+use lox::{
+    ast,
+    diagnostics::{self, Diagnostic},
+    interpreter::Interpreter,
+    parser::{
+        error::ParseError,
+        scanner::{error::ScanError, Scanner},
+        state::ParserOptions,
+        Parser,
+    },
+    resolver::Resolver,
+    span::LineMap,
+    token::TokenKind,
+    user,
+    user::repl::Repl,
+    vm::{compiler::Compiler, Vm},
+};
+
+enum Mode {
+    Run,
+    Tokens(String),
+    Ast(String),
+    Resolve(String),
+    Disassemble(String),
+}
 
 fn main() -> io::Result<()> {
-    match env::args().nth(1) {
-        Some(path) => user::run_file(path),
-        _ => user::run_repl(),
+    let mut args = env::args().skip(1);
+    let mut backend_is_vm = false;
+    let mut paren_free_conditions = false;
+    let mut path = None;
+    let mut mode = Mode::Run;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--backend=vm" => backend_is_vm = true,
+            "--backend=treewalk" => backend_is_vm = false,
+            "--paren-free-conditions" => paren_free_conditions = true,
+            "--tokens" => mode = Mode::Tokens(expect_path(&mut args, "--tokens")),
+            "--ast" => mode = Mode::Ast(expect_path(&mut args, "--ast")),
+            "--resolve" => mode = Mode::Resolve(expect_path(&mut args, "--resolve")),
+            "--disassemble" => mode = Mode::Disassemble(expect_path(&mut args, "--disassemble")),
+            _ => path = Some(arg),
+        }
+    }
+
+    match mode {
+        Mode::Tokens(file) => dump_tokens(&file),
+        Mode::Ast(file) => dump_ast(&file),
+        Mode::Resolve(file) => dump_resolved(&file),
+        Mode::Disassemble(file) => dump_disassembly(&file),
+        Mode::Run if backend_is_vm => run_vm(path),
+        Mode::Run => match path {
+            Some(path) => user::run_file_with_options(
+                path,
+                None,
+                ParserOptions {
+                    paren_free_conditions,
+                    ..ParserOptions::default()
+                },
+            )
+            .map(|_| ()),
+            None => Repl::run(),
+        },
+    }
+}
+
+fn expect_path(args: &mut impl Iterator<Item = String>, flag: &str) -> String {
+    args.next().unwrap_or_else(|| {
+        eprintln!("`{}` requires a file path argument", flag);
+        process::exit(1);
+    })
+}
+
+/// `--tokens <file>`: scans `file` and prints its raw token stream, the same debug view the REPL's
+/// `:lex` command offers. Any lexical error shows up inline as a `TokenKind::Error`, which is
+/// additionally rendered as a diagnostic and causes a non-zero exit.
+fn dump_tokens(file: &str) -> io::Result<()> {
+    let source = fs::read_to_string(file)?;
+
+    let mut errors = Vec::new();
+    for token in Scanner::new(&source) {
+        if let TokenKind::Error(error) = &token.kind {
+            errors.push(ParseError::ScanError {
+                error: error.clone(),
+                span: token.span,
+                note: None,
+            });
+        }
+    }
+
+    ast::dbg::print_scanned_tokens(&source);
+    exit_on_errors(&source, &errors)
+}
+
+/// `--ast <file>`: parses `file` and prints its syntax tree via `TreePrinter`, the same debug view
+/// the REPL's `:ast` command offers. Parse errors are rendered as diagnostics and exit non-zero
+/// without printing a (necessarily incomplete) tree.
+fn dump_ast(file: &str) -> io::Result<()> {
+    let source = fs::read_to_string(file)?;
+    let (stmts, errors) = Parser::new(&source).parse();
+    exit_on_errors(&source, &errors)?;
+    ast::dbg::print_program_tree(&stmts);
+    Ok(())
+}
+
+/// `--resolve <file>`: parses and resolves `file`, then prints its syntax tree annotated with the
+/// scope distance the `Resolver` computed for every binding use.
+fn dump_resolved(file: &str) -> io::Result<()> {
+    let source = fs::read_to_string(file)?;
+    let (stmts, errors) = Parser::new(&source).parse();
+    exit_on_errors(&source, &errors)?;
+
+    let mut interpreter = Interpreter::new();
+    let globals = interpreter.global_names();
+    let (ok, errors, warnings) = Resolver::new(&mut interpreter, globals).resolve(&stmts);
+    if !warnings.is_empty() {
+        let mut diagnostics: Vec<Diagnostic> = warnings
+            .iter()
+            .map(|w| Diagnostic::warning(w.span, w.message.clone()))
+            .collect();
+        diagnostics::render_all(&mut io::stderr(), &source, &mut diagnostics);
+    }
+    if !ok {
+        let mut diagnostics: Vec<Diagnostic> = errors.iter().map(Diagnostic::from).collect();
+        diagnostics::render_all(&mut io::stderr(), &source, &mut diagnostics);
+        process::exit(1);
+    }
+
+    ast::dbg::print_resolved_tree(&stmts, &interpreter);
+    Ok(())
+}
+
+/// `--disassemble <file>`: compiles `file` through the bytecode `Compiler` and prints its `Chunk`,
+/// one line per instruction (byte offset, source line, opcode mnemonic and operand). Unlike
+/// `Compiler::compile`'s own built-in trace (only compiled in under the `disassemble` feature),
+/// this mode is always available, since it's the only way to inspect the bytecode a given script
+/// produces without rebuilding the crate with that feature enabled.
+fn dump_disassembly(file: &str) -> io::Result<()> {
+    let source = fs::read_to_string(file)?;
+    match Compiler::compile(&source) {
+        Ok(chunk) => {
+            print!("{}", chunk.disassemble("code", &LineMap::new(&source)));
+            Ok(())
+        }
+        Err(errors) => {
+            let mut diagnostics: Vec<Diagnostic> = errors.iter().map(Diagnostic::from).collect();
+            diagnostics::render_all(&mut io::stderr(), &source, &mut diagnostics);
+            process::exit(1);
+        }
+    }
+}
+
+fn exit_on_errors(source: &str, errors: &[ParseError]) -> io::Result<()> {
+    if !errors.is_empty() {
+        let mut diagnostics: Vec<Diagnostic> = errors.iter().map(Diagnostic::from).collect();
+        diagnostics::render_all(&mut io::stderr(), source, &mut diagnostics);
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// Runs the bytecode VM backend, which only supports a subset of the language so far (see
+/// `lox::vm` for the current limitations).
+fn run_vm(path: Option<String>) -> io::Result<()> {
+    let source = match path {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => {
+            eprintln!("The `--backend=vm` flag requires a script path (use `:backend vm` inside the REPL instead)");
+            process::exit(1);
+        }
+    };
+
+    if let Err(error) = Vm::new().interpret(&source) {
+        error.render(&mut io::stderr(), &source);
+        process::exit(1);
     }
+    Ok(())
 }
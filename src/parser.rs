@@ -6,7 +6,11 @@ use crate::{
         stmt::{self, Stmt},
     },
     data::{LoxIdent, LoxValue},
-    parser::{error::ParseError, scanner::Scanner, state::ParserOptions},
+    parser::{
+        error::{ErrorReporter, ParseError},
+        scanner::Scanner,
+        state::{ParserOptions, Restrictions},
+    },
     span::Span,
     token::{Token, TokenKind},
 };
@@ -20,11 +24,23 @@ type PResult<T> = Result<T, ParseError>;
 
 pub type ParserOutcome = (Vec<Stmt>, Vec<ParseError>);
 
-pub struct Parser<'src> {
+pub struct Parser<'src, E: ErrorReporter = Vec<ParseError>> {
     scanner: Peekable<Scanner<'src>>,
     current_token: Token,
     prev_token: Token,
-    diagnostics: Vec<ParseError>,
+    reporter: E,
+    /// Number of diagnostics actually handed to `reporter` so far, used to enforce
+    /// `MAX_DIAGNOSTICS`. Tracked separately because a generic `E: ErrorReporter` has no `len()` to
+    /// fall back on the way the old `Vec<ParseError>`-only field did.
+    errors_reported: usize,
+    /// Set by `push_diagnostic` the moment a diagnostic is recorded, cleared by `synchronize`
+    /// once a statement boundary is reached and by `parse_decl` after it reports the error that
+    /// started the skip-ahead (`push_recovered_diagnostic`, for a mistake the parser already
+    /// repaired inline, leaves it as it was found). Suppresses the storm of spurious follow-on
+    /// errors a single bad token can otherwise trigger while the parser is skipping ahead to
+    /// resynchronize.
+    panic: bool,
+    restrictions: Restrictions,
     pub options: ParserOptions,
 }
 
@@ -41,9 +57,11 @@ pub struct Parser<'src> {
 //                 | stmt ;
 //
 // var_decl      ::= "var" IDENTIFIER ( "=" expr )? ";" ;
-// class_decl    ::= "class" IDENTIFIER "{" fn* "}" ;
+// class_decl    ::= "class" IDENTIFIER ( "<" IDENTIFIER )? "{" method* "}" ;
 // fun_decl      ::= "fun" fn ;
 //
+// method        ::= "class" fn | getter | fn ;
+// getter        ::= IDENTIFIER block_stmt ;
 // fn            ::= IDENTIFIER "(" params? ")" block_stmt ;
 // params        ::= IDENTIFIER ( "," IDENTIFIER )* ;
 //
@@ -82,14 +100,16 @@ pub struct Parser<'src> {
 //                 | NUMBER | STRING
 //                 | "true" | "false"
 //                 | "nil"
+//                 | "this"
+//                 | "super" "." IDENTIFIER
 //                 | "(" expr ")" ;
 //
 // -----------------------------------------------------------------------------
 //
 // Each production has a correspondent method in the following implementation.
-impl Parser<'_> {
-    pub fn parse(mut self) -> ParserOutcome {
-        (self.parse_program(), self.diagnostics)
+impl<E: ErrorReporter> Parser<'_, E> {
+    pub fn parse(mut self) -> (Vec<Stmt>, E) {
+        (self.parse_program(), self.reporter)
     }
 
     fn parse_program(&mut self) -> Vec<Stmt> {
@@ -116,8 +136,16 @@ impl Parser<'_> {
         match result {
             Ok(stmt) => stmt,
             Err(error) => {
-                self.diagnostics.push(error);
-                self.synchronize();
+                // Entering panic mode before skipping ahead suppresses any incidental noise (e.g.
+                // a run of lexical errors in garbage tokens) encountered while resynchronizing;
+                // `synchronize` clears it once a boundary is reached, so the actual error below is
+                // still always reported.
+                self.panic = true;
+                let resume_at = self.synchronize();
+                let error = error.with_note(format!("parsing resumed at position {}", resume_at));
+                self.push_diagnostic(error);
+                // Reporting it set panic mode again; the skip-ahead is over, so clear it.
+                self.panic = false;
                 let lo = self.current_token.span.lo;
                 Stmt::new(Span::new(lo, lo), stmt::Dummy())
             }
@@ -147,6 +175,11 @@ impl Parser<'_> {
 
         let name = self.consume_ident("Expected class name")?;
 
+        let super_name = self
+            .take(Less)
+            .then(|| self.consume_ident("Expected superclass name"))
+            .transpose()?;
+
         let (methods, class_body_span) = self.paired_spanned(
             LeftBrace,
             "Expected `{` before class body",
@@ -154,7 +187,7 @@ impl Parser<'_> {
             |this| {
                 let mut methods = Vec::new();
                 while !this.is(RightBrace) && !this.is_at_end() {
-                    methods.push(this.parse_fn_params_and_body("method")?);
+                    methods.push(this.parse_method()?);
                 }
                 Ok(methods)
             },
@@ -162,7 +195,11 @@ impl Parser<'_> {
 
         Ok(Stmt::new(
             class_span.to(class_body_span),
-            stmt::ClassDecl { name, methods },
+            stmt::ClassDecl {
+                name,
+                super_name,
+                methods,
+            },
         ))
     }
 
@@ -174,10 +211,66 @@ impl Parser<'_> {
     }
 
     fn parse_fn_params_and_body(&mut self, kind: &'static str) -> PResult<stmt::FunDecl> {
-        use TokenKind::*;
         let name = self.consume_ident(format!("Expected {} name", kind))?;
+        let params = self.parse_params(kind)?;
+        let (body, body_span) = self.parse_block()?;
+        Ok(stmt::FunDecl {
+            span: name.span.to(body_span),
+            name,
+            params,
+            body,
+            kind: stmt::MethodKind::Function,
+        })
+    }
+
+    /// Parses a single class member: a static method (a leading `class` keyword), a getter (a
+    /// name directly followed by `{`, with no parameter list), or an ordinary method.
+    fn parse_method(&mut self) -> PResult<stmt::FunDecl> {
+        use TokenKind::*;
+
+        if self.take(Class) {
+            let name = self.consume_ident("Expected static method name")?;
+            let params = self.parse_params("static method")?;
+            let (body, body_span) = self.parse_block()?;
+            return Ok(stmt::FunDecl {
+                span: name.span.to(body_span),
+                name,
+                params,
+                body,
+                kind: stmt::MethodKind::Static,
+            });
+        }
+
+        let name = self.consume_ident("Expected method name")?;
+
+        if self.is(LeftBrace) {
+            let (body, body_span) = self.parse_block()?;
+            return Ok(stmt::FunDecl {
+                span: name.span.to(body_span),
+                name,
+                params: Vec::new(),
+                body,
+                kind: stmt::MethodKind::Getter,
+            });
+        }
 
-        let params = self.paired(
+        let params = self.parse_params("method")?;
+        let (body, body_span) = self.parse_block()?;
+        Ok(stmt::FunDecl {
+            span: name.span.to(body_span),
+            name,
+            params,
+            body,
+            kind: stmt::MethodKind::Function,
+        })
+    }
+
+    /// Parses a parenthesized, comma-separated parameter list, shared by top-level functions,
+    /// methods and static methods. `kind` is only used to word the diagnostics (e.g. "function",
+    /// "method", "static method").
+    fn parse_params(&mut self, kind: &'static str) -> PResult<Vec<LoxIdent>> {
+        use TokenKind::*;
+        self.paired(
             LeftParen,
             format!("Expected `(` after {} name", kind),
             format!("Expected `)` after {} parameter list", kind),
@@ -187,22 +280,18 @@ impl Parser<'_> {
                     loop {
                         let param = this.consume_ident("Expected parameter name")?;
                         params.push(param);
-                        if !this.take(Comma) {
-                            break;
+                        if this.take(Comma) {
+                            continue;
                         }
+                        if this.missing_separator_before_more("Expected `,` between parameters") {
+                            continue;
+                        }
+                        break;
                     }
                 }
                 Ok(params)
             },
-        )?;
-
-        let (body, body_span) = self.parse_block()?;
-        Ok(stmt::FunDecl {
-            span: name.span.to(body_span),
-            name,
-            params,
-            body,
-        })
+        )
     }
 
     //
@@ -216,6 +305,8 @@ impl Parser<'_> {
             For => self.parse_for_stmt(),
             While => self.parse_while_stmt(),
             Return => self.parse_return_stmt(),
+            Break => self.parse_break_stmt(),
+            Continue => self.parse_continue_stmt(),
             Print => self.parse_print_stmt(),
             LeftBrace => {
                 let (stmts, span) = self.parse_block()?;
@@ -229,11 +320,9 @@ impl Parser<'_> {
         use TokenKind::*;
         let if_token_span = self.consume(If, S_MUST)?.span;
 
-        let cond = self.paired(
-            LeftParen,
-            "Expected `if` condition group opening",
+        let cond = self.parse_cond(
+            "Expected `(` before `if` condition",
             "Expected `if` condition group to be closed",
-            |this| this.parse_expr(),
         )?;
         let then_branch = self.parse_stmt()?;
         let else_branch = self.take(Else).then(|| self.parse_stmt()).transpose()?;
@@ -346,11 +435,9 @@ impl Parser<'_> {
         use TokenKind::*;
         let while_token_span = self.consume(While, S_MUST)?.span;
 
-        let cond = self.paired(
-            LeftParen,
-            "Expected `while` condition group opening",
+        let cond = self.parse_cond(
+            "Expected `(` before `while` condition",
             "Expected `while` condition group to be closed",
-            |this| this.parse_expr(),
         )?;
         let body = self.parse_stmt()?;
 
@@ -380,6 +467,30 @@ impl Parser<'_> {
         ))
     }
 
+    fn parse_break_stmt(&mut self) -> PResult<Stmt> {
+        let break_span = self.consume(TokenKind::Break, S_MUST)?.span;
+        let semicolon_span = self
+            .consume(TokenKind::Semicolon, "Expected `;` after break")?
+            .span;
+
+        Ok(Stmt::new(
+            break_span.to(semicolon_span),
+            stmt::Break { break_span },
+        ))
+    }
+
+    fn parse_continue_stmt(&mut self) -> PResult<Stmt> {
+        let continue_span = self.consume(TokenKind::Continue, S_MUST)?.span;
+        let semicolon_span = self
+            .consume(TokenKind::Semicolon, "Expected `;` after continue")?
+            .span;
+
+        Ok(Stmt::new(
+            continue_span.to(semicolon_span),
+            stmt::Continue { continue_span },
+        ))
+    }
+
     fn parse_print_stmt(&mut self) -> PResult<Stmt> {
         let print_token_span = self.consume(TokenKind::Print, S_MUST)?.span;
 
@@ -418,11 +529,24 @@ impl Parser<'_> {
             return Ok(Stmt::new(expr.span, stmt::Print { expr, debug: true }));
         }
 
-        let semicolon_span = self
-            .consume(TokenKind::Semicolon, "Expected `;` after expression")?
-            .span;
+        if self.take(TokenKind::Semicolon) {
+            let span = expr.span.to(self.prev_token.span);
+            return Ok(Stmt::new(span, stmt::Expr { expr }));
+        }
 
-        Ok(Stmt::new(expr.span.to(semicolon_span), stmt::Expr { expr }))
+        // A missing `;` is common enough (and unambiguous enough once the next token plainly
+        // starts a new statement) that desyncing the whole parser over it is overkill: report it
+        // and keep the expression as-is, rather than falling through to `synchronize()`.
+        if self.starts_new_stmt() {
+            self.push_recovered_diagnostic(ParseError::Error {
+                message: "Expected `;` after expression".into(),
+                span: self.current_token.span,
+                note: None,
+            });
+            return Ok(Stmt::new(expr.span, stmt::Expr { expr }));
+        }
+
+        Err(self.unexpected("Expected `;` after expression", Some(TokenKind::Semicolon)))
     }
 
     //
@@ -436,7 +560,7 @@ impl Parser<'_> {
     fn parse_assignment(&mut self) -> PResult<Expr> {
         // The parser does not yet know if `left` should be used as an expression (i.e. an rvalue)
         // or as an "assignment target" (i.e. an lvalue).
-        let left = self.parse_or()?;
+        let left = self.parse_expr_bp(Precedence::None)?;
 
         if self.take(TokenKind::Equal) {
             // Since assignments are right associative, we use right recursion to parse its value.
@@ -465,6 +589,7 @@ impl Parser<'_> {
                 _ => Err(ParseError::Error {
                     message: "Invalid assignment target".into(),
                     span: left.span,
+                    note: None,
                 }),
             }
         } else {
@@ -472,58 +597,52 @@ impl Parser<'_> {
         }
     }
 
-    fn parse_or(&mut self) -> PResult<Expr> {
-        bin_expr!(
-            self,
-            parse_as = Logical,
-            token_kinds = Or,
-            next_production = parse_and
-        )
-    }
-
-    fn parse_and(&mut self) -> PResult<Expr> {
-        bin_expr!(
-            self,
-            parse_as = Logical,
-            token_kinds = And,
-            next_production = parse_equality
-        )
-    }
-
-    fn parse_equality(&mut self) -> PResult<Expr> {
-        bin_expr!(
-            self,
-            parse_as = Binary,
-            token_kinds = EqualEqual | BangEqual,
-            next_production = parse_comparison
-        )
-    }
-
-    fn parse_comparison(&mut self) -> PResult<Expr> {
-        bin_expr!(
-            self,
-            parse_as = Binary,
-            token_kinds = Greater | GreaterEqual | Less | LessEqual,
-            next_production = parse_term
-        )
-    }
-
-    fn parse_term(&mut self) -> PResult<Expr> {
-        bin_expr!(
-            self,
-            parse_as = Binary,
-            token_kinds = Plus | Minus,
-            next_production = parse_factor
-        )
-    }
+    /// Parses an infix expression via precedence climbing: repeatedly parses a unary operand,
+    /// then consumes and folds in any infix operator whose precedence is at least `min_prec`,
+    /// recursing for its right-hand operand with the precedence `AssocOp` says that operator's
+    /// fixity requires. Replaces what used to be a chain of six near-identical `parse_or` →
+    /// `parse_and` → ... → `parse_factor` methods (one per precedence level) with a single loop
+    /// driven by `AssocOp::of`'s operator table, the same technique rustc's own expression parser
+    /// uses.
+    fn parse_expr_bp(&mut self, min_prec: Precedence) -> PResult<Expr> {
+        let mut left = self.parse_unary()?;
+
+        while let Some((op, prec)) = AssocOp::of(&self.current_token.kind) {
+            if prec < min_prec {
+                break;
+            }
+            let operator = self.advance().clone();
+            // Left-associative operators recurse requiring a strictly tighter precedence, so a
+            // same-precedence operator to the right stops and folds in here instead; right-
+            // associative operators recurse at the same `prec`, so a same-precedence operator to
+            // the right keeps nesting there instead.
+            let next_min_prec = match op.fixity() {
+                Fixity::Left => prec.next(),
+                Fixity::Right => prec,
+            };
+            let right = self.parse_expr_bp(next_min_prec)?;
+            let span = left.span.to(right.span);
+            left = match op {
+                AssocOp::Logical(_) => Expr::new(
+                    span,
+                    expr::Logical {
+                        left: left.into(),
+                        operator,
+                        right: right.into(),
+                    },
+                ),
+                AssocOp::Binary(_) => Expr::new(
+                    span,
+                    expr::Binary {
+                        left: left.into(),
+                        operator,
+                        right: right.into(),
+                    },
+                ),
+            };
+        }
 
-    fn parse_factor(&mut self) -> PResult<Expr> {
-        bin_expr!(
-            self,
-            parse_as = Binary,
-            token_kinds = Star | Slash,
-            next_production = parse_unary
-        )
+        Ok(left)
     }
 
     fn parse_unary(&mut self) -> PResult<Expr> {
@@ -539,6 +658,26 @@ impl Parser<'_> {
                 },
             ));
         }
+
+        // A comparison/equality operator in operand position means the left-hand operand was
+        // simply omitted (e.g. a stray `<= 3;`). Report it, drop the operator and parse on with
+        // the right-hand operand that follows as the expression, instead of failing the whole
+        // expression with an unhelpful "expected any expression" pointing at the operator itself.
+        if let EqualEqual | BangEqual | Less | LessEqual | Greater | GreaterEqual =
+            self.current_token.kind
+        {
+            let operator = self.advance().clone();
+            self.push_recovered_diagnostic(ParseError::Error {
+                message: format!(
+                    "Binary operator `{}` used without a left-hand operand",
+                    operator.kind
+                ),
+                span: operator.span,
+                note: None,
+            });
+            return self.parse_unary();
+        }
+
         self.parse_call_or_get()
     }
 
@@ -574,9 +713,13 @@ impl Parser<'_> {
                 if !this.is(RightParen) {
                     loop {
                         args.push(this.parse_expr()?);
-                        if !this.take(Comma) {
-                            break;
+                        if this.take(Comma) {
+                            continue;
+                        }
+                        if this.missing_separator_before_more("Expected `,` between arguments") {
+                            continue;
                         }
+                        break;
                     }
                 }
                 Ok(args)
@@ -584,9 +727,10 @@ impl Parser<'_> {
         )?;
 
         if args.len() >= 255 {
-            self.diagnostics.push(ParseError::Error {
+            self.push_recovered_diagnostic(ParseError::Error {
                 message: "Call can't have more than 255 arguments".into(),
                 span: call_span,
+                note: None,
             })
         }
 
@@ -610,29 +754,78 @@ impl Parser<'_> {
                 let name = self.consume_ident(S_MUST)?;
                 Ok(Expr::new(name.span, expr::Var { name }))
             }
+            This => {
+                let token = self.advance();
+                let span = token.span;
+                Ok(Expr::new(span, expr::This { name: LoxIdent::new(span, "this") }))
+            }
+            Super => {
+                let super_span = self.advance().span;
+                self.consume(Dot, "Expected `.` after `super`")?;
+                let method = self.consume_ident("Expected superclass method name")?;
+                let span = super_span.to(method.span);
+                Ok(Expr::new(
+                    span,
+                    expr::Super {
+                        super_ident: LoxIdent::new(super_span, "super"),
+                        method,
+                    },
+                ))
+            }
             LeftParen => {
+                // An explicit group re-enables full expression syntax for its own contents, the
+                // same way rustc's parser clears its restrictions flag inside parens.
+                let outer_restrictions = mem::replace(&mut self.restrictions, Restrictions::NONE);
                 let (expr, span) = self.paired_spanned(
                     LeftParen,
                     S_MUST,
                     "Expected group to be closed",
                     |this| this.parse_expr(),
                 )?;
+                self.restrictions = outer_restrictions;
                 Ok(Expr::new(span, expr::Group { expr: expr.into() }))
             }
-            _ => Err(self.unexpected("Expected any expression", None)),
+            _ => {
+                // Unlike every other production, a missing primary expression doesn't unwind via
+                // `?`: it reports the error and hands back an `Expr::Error` placeholder without
+                // consuming the offending token, so a caller that only needed *some* expression
+                // here (a binary
+                // operand, a call argument, a var initializer) can keep parsing the surrounding
+                // construct instead of discarding it entirely as a `Stmt::Dummy`.
+                let span = self.current_token.span;
+                let error = self.unexpected("Expected any expression", None);
+                // This recovery hands back a placeholder without consuming the offending token or
+                // unwinding to `synchronize()`, so panic mode has to be cleared here instead —
+                // left set, it would silently swallow the next, unrelated real error.
+                self.push_recovered_diagnostic(error);
+                Ok(Expr::new(span, expr::Error()))
+            }
         }
     }
 }
 
 // The parser helper methods.
 impl<'src> Parser<'src> {
-    /// Creates a new parser.
+    /// Creates a new parser that buffers its diagnostics into a `Vec<ParseError>`, returned
+    /// alongside the parsed statements by `parse`. This is the common case; use
+    /// `Parser::with_reporter` to plug in a different `ErrorReporter`.
     pub fn new(src: &'src str) -> Self {
+        Self::with_reporter(src, Vec::new())
+    }
+}
+
+impl<'src, E: ErrorReporter> Parser<'src, E> {
+    /// Creates a new parser that hands each diagnostic to the given `ErrorReporter` as soon as
+    /// it's produced, instead of always buffering into a `Vec`.
+    pub fn with_reporter(src: &'src str, reporter: E) -> Self {
         let mut parser = Self {
             scanner: Scanner::new(src).peekable(),
             current_token: Token::dummy(),
             prev_token: Token::dummy(),
-            diagnostics: Vec::new(),
+            reporter,
+            errors_reported: 0,
+            panic: false,
+            restrictions: Restrictions::NONE,
             options: ParserOptions::default(),
         };
         parser.advance(); // The first advancement.
@@ -645,9 +838,13 @@ impl<'src> Parser<'src> {
             let maybe_next = self.scanner.next().expect("Cannot advance past Eof.");
             // Report and ignore tokens with the `Error` kind:
             if let TokenKind::Error(error) = maybe_next.kind {
-                self.diagnostics.push(ParseError::ScanError {
+                // Scanning just skips the offending token and keeps going, rather than unwinding
+                // to `synchronize()`, so panic mode has to be cleared here instead — left set, it
+                // would silently swallow the next, unrelated real error.
+                self.push_recovered_diagnostic(ParseError::ScanError {
                     error,
                     span: maybe_next.span,
+                    note: None,
                 });
                 continue;
             }
@@ -743,6 +940,106 @@ impl<'src> Parser<'src> {
         Ok((ret, start_span.to(end_span)))
     }
 
+    /// Parses a `(` cond `)` group for `if`/`while`, tolerating a missing opening `(`: if what
+    /// follows still looks like an expression, the condition is parsed anyway and a diagnostic is
+    /// pushed instead of aborting the statement, since this is an easy typo to both make and
+    /// repair. A trailing `)` is consumed if present either way, but isn't required when `(` was
+    /// never opened in the first place.
+    fn parse_paren_cond(
+        &mut self,
+        missing_open_msg: impl Into<String>,
+        close_msg: impl Into<String>,
+    ) -> PResult<Expr> {
+        use TokenKind::*;
+        if self.take(LeftParen) {
+            let cond = self.parse_expr()?;
+            self.consume(RightParen, close_msg)?;
+            return Ok(cond);
+        }
+
+        if !self.can_start_expr() {
+            return Err(self.unexpected(missing_open_msg, Some(LeftParen)));
+        }
+
+        self.push_recovered_diagnostic(ParseError::Error {
+            message: missing_open_msg.into(),
+            span: self.current_token.span,
+            note: None,
+        });
+        let cond = self.parse_expr()?;
+        self.take(RightParen);
+        Ok(cond)
+    }
+
+    /// Parses an `if`/`while` condition, honoring `ParserOptions::paren_free_conditions`: when
+    /// it's set, the parentheses are entirely optional rather than merely recovered from when
+    /// missing (see `parse_paren_cond`), since a bare condition is legal syntax, not a mistake.
+    fn parse_cond(
+        &mut self,
+        missing_open_msg: impl Into<String>,
+        close_msg: impl Into<String>,
+    ) -> PResult<Expr> {
+        if self.options.paren_free_conditions {
+            return self.parse_restricted_cond_expr();
+        }
+        self.parse_paren_cond(missing_open_msg, close_msg)
+    }
+
+    /// Parses a condition expression under `Restrictions::NO_BLOCK_AS_EXPR` and
+    /// `Restrictions::NO_GROUP_LITERAL`, so the `{` opening the following statement block is
+    /// never at risk of being consumed as part of the condition itself. A `(...)` group, parsed
+    /// via `parse_primary`, clears both restrictions for its own contents.
+    fn parse_restricted_cond_expr(&mut self) -> PResult<Expr> {
+        let outer_restrictions = mem::replace(
+            &mut self.restrictions,
+            self.restrictions
+                .union(Restrictions::NO_BLOCK_AS_EXPR)
+                .union(Restrictions::NO_GROUP_LITERAL),
+        );
+        let cond = self.parse_expr();
+        self.restrictions = outer_restrictions;
+        cond
+    }
+
+    /// In a comma-separated list (call arguments, parameter lists), checks whether the separating
+    /// `,` was simply omitted: the list hasn't been closed yet, but another item follows right
+    /// away. If so, pushes a diagnostic for the missing separator and returns `true` so the caller
+    /// can keep parsing the list as if a `,` had been there.
+    fn missing_separator_before_more(&mut self, message: impl Into<String>) -> bool {
+        if self.is(TokenKind::RightParen) || !self.can_start_expr() {
+            return false;
+        }
+        self.push_recovered_diagnostic(ParseError::Error {
+            message: message.into(),
+            span: self.current_token.span,
+            note: None,
+        });
+        true
+    }
+
+    /// Checks whether the current token could plausibly begin an expression (i.e. one of
+    /// `parse_unary`/`parse_primary`'s own lead tokens). Used by recovery points to decide whether
+    /// it's worth attempting to parse on, rather than giving up immediately.
+    fn can_start_expr(&self) -> bool {
+        use TokenKind::*;
+        matches!(
+            self.current_token.kind,
+            Identifier(_) | String(_) | Number(_) | True | False | Nil | This | Super | LeftParen
+                | Bang | Minus | Typeof | Show
+        )
+    }
+
+    /// Checks whether the current token plainly marks the start of a new statement, the same set
+    /// `synchronize` resumes at.
+    fn starts_new_stmt(&self) -> bool {
+        use TokenKind::*;
+        self.is_at_end()
+            || matches!(
+                self.current_token.kind,
+                Class | For | Fun | If | Print | Return | Var | While
+            )
+    }
+
     /// Returns an `ParseError::UnexpectedToken`.
     #[inline(always)]
     fn unexpected(&self, message: impl Into<String>, expected: Option<TokenKind>) -> ParseError {
@@ -750,6 +1047,7 @@ impl<'src> Parser<'src> {
             message: message.into(),
             expected,
             offending: self.current_token.clone(),
+            note: None,
         }
     }
 
@@ -770,20 +1068,63 @@ impl<'src> Parser<'src> {
     ///   * If the next token marks the start of a new statement.
     ///
     /// Before synchronize one must not forget to emit the raised parse error.
-    fn synchronize(&mut self) {
-        use TokenKind::*;
+    ///
+    /// Returns the span parsing actually resumed from, so the caller can attach it to the error
+    /// as a recovery note.
+    fn synchronize(&mut self) -> Span {
         while !self.is_at_end() {
-            match &self.current_token.kind {
-                Semicolon => {
-                    self.advance();
-                    return;
-                }
-                Class | For | Fun | If | Print | Return | Var | While => {
-                    return;
-                }
-                _ => self.advance(),
-            };
+            if self.starts_new_stmt() {
+                self.panic = false;
+                return self.current_token.span;
+            }
+            if self.current_token.kind == TokenKind::Semicolon {
+                self.advance();
+                self.panic = false;
+                return self.current_token.span;
+            }
+            self.advance();
         }
+        self.panic = false;
+        self.current_token.span
+    }
+
+    /// Hands a diagnostic to `self.reporter`, capping the total reported at `MAX_DIAGNOSTICS` so a
+    /// sufficiently broken file can't turn "collect every error" into an unreadable wall of
+    /// output. A single final note marks the cutoff; diagnostics past it are dropped silently.
+    fn push_diagnostic(&mut self, error: ParseError) {
+        if self.panic {
+            return;
+        }
+        self.panic = true;
+        match self.errors_reported.cmp(&MAX_DIAGNOSTICS) {
+            std::cmp::Ordering::Less => {
+                self.reporter.report(error);
+                self.errors_reported += 1;
+            }
+            std::cmp::Ordering::Equal => {
+                self.reporter.report(ParseError::Error {
+                    message: format!(
+                        "Too many errors (over {}); remaining ones are suppressed",
+                        MAX_DIAGNOSTICS
+                    ),
+                    span: self.current_token.span,
+                    note: None,
+                });
+                self.errors_reported += 1;
+            }
+            std::cmp::Ordering::Greater => (),
+        }
+    }
+
+    /// Like `push_diagnostic`, but for a mistake the parser has already repaired inline, so
+    /// parsing continues normally rather than via `synchronize()`. Panic mode is left as it was
+    /// found: outside a resynchronization there's no cascading failure in flight to suppress
+    /// follow-on noise for, and leaving it set would silently swallow the next, unrelated real
+    /// error; during `synchronize`'s skip-ahead the diagnostic is suppressed as follow-on noise.
+    fn push_recovered_diagnostic(&mut self, error: ParseError) {
+        let was_panicking = self.panic;
+        self.push_diagnostic(error);
+        self.panic = was_panicking;
     }
 
     /// Checks if the parser has finished.
@@ -796,23 +1137,93 @@ impl<'src> Parser<'src> {
 /// (String Must) Indicates the parser to emit a parser error (i.e. the parser is bugged) message.
 const S_MUST: &str = "Parser bug. Unexpected token";
 
-/// Parses a binary expression.
-macro_rules! bin_expr {
-    ($self:expr, parse_as = $ast_kind:ident, token_kinds = $( $kind:ident )|+, next_production = $next:ident) => {{
-        let mut expr = $self.$next()?;
-        while let $( TokenKind::$kind )|+ = $self.current_token.kind {
-            let operator = $self.advance().clone();
-            let right = $self.$next()?;
-            expr = Expr::new(
-                expr.span.to(right.span),
-                expr::$ast_kind {
-                    left: expr.into(),
-                    operator,
-                    right: right.into(),
-                },
-            );
+/// Upper bound on the number of diagnostics collected over one parse, guarding against a
+/// sufficiently broken file turning "report every error" into an unreadable wall of output. See
+/// `Parser::push_diagnostic`.
+const MAX_DIAGNOSTICS: usize = 200;
+
+/// Associativity of an infix operator, i.e. which side a chain of same-precedence operators
+/// nests on: `a op b op c` is `(a op b) op c` for `Left`, `a op (b op c)` for `Right`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Fixity {
+    Left,
+    Right,
+}
+
+/// The full precedence ladder, lowest to highest. `AssocOp::of` only ever hands out the levels
+/// that name an actual infix operator (`Pipe` .. `Factor`); the remaining variants exist so the
+/// ladder reads as one coherent table rather than a handful of disconnected infix tiers, and so
+/// `Precedence::next` has a real "one level tighter" value for every entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    None,
+    Assignment,
+    Pipe,
+    Or,
+    And,
+    Equality,
+    Comparison,
+    Term,
+    Factor,
+    Unary,
+    Call,
+    Primary,
+}
+
+impl Precedence {
+    /// The next tighter precedence level, used to bump a left-associative operator's
+    /// right-hand-side minimum so a same-precedence operator to the right stops and folds in at
+    /// the current level instead of nesting further. Saturates at `Primary`, the tightest level.
+    fn next(self) -> Precedence {
+        use Precedence::*;
+        match self {
+            None => Assignment,
+            Assignment => Pipe,
+            Pipe => Or,
+            Or => And,
+            And => Equality,
+            Equality => Comparison,
+            Comparison => Term,
+            Term => Factor,
+            Factor => Unary,
+            Unary => Call,
+            Call | Primary => Primary,
         }
-        Ok(expr)
-    }};
+    }
+}
+
+/// Every infix operator `parse_expr_bp` understands, tagged with the `ExprKind` it should build.
+/// Lox has no right-associative infix operator today, but the split keeps `fixity()` honest about
+/// the one place that would need to change to add one (e.g. a `**` exponent).
+#[derive(Debug, Clone, Copy)]
+enum AssocOp {
+    Logical(Fixity),
+    Binary(Fixity),
+}
+
+impl AssocOp {
+    /// Looks up `kind`'s `AssocOp` and precedence, or `None` if it isn't an infix operator at all
+    /// (in which case `parse_expr_bp`'s loop simply stops). Higher precedence binds tighter, so
+    /// this table is the single place precedence lives; adding an operator is one new arm here.
+    fn of(kind: &TokenKind) -> Option<(AssocOp, Precedence)> {
+        use TokenKind::*;
+        Some(match kind {
+            Pipe => (AssocOp::Binary(Fixity::Left), Precedence::Pipe),
+            Or => (AssocOp::Logical(Fixity::Left), Precedence::Or),
+            And => (AssocOp::Logical(Fixity::Left), Precedence::And),
+            EqualEqual | BangEqual => (AssocOp::Binary(Fixity::Left), Precedence::Equality),
+            Greater | GreaterEqual | Less | LessEqual => {
+                (AssocOp::Binary(Fixity::Left), Precedence::Comparison)
+            }
+            Plus | Minus => (AssocOp::Binary(Fixity::Left), Precedence::Term),
+            Star | Slash => (AssocOp::Binary(Fixity::Left), Precedence::Factor),
+            _ => return None,
+        })
+    }
+
+    fn fixity(self) -> Fixity {
+        match self {
+            AssocOp::Logical(fixity) | AssocOp::Binary(fixity) => fixity,
+        }
+    }
 }
-use bin_expr;
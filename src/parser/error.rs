@@ -4,6 +4,7 @@ use std::{
 };
 
 use crate::{
+    diagnostics::Diagnostic,
     parser::scanner::error::ScanError,
     span::Span,
     token::{Token, TokenKind},
@@ -14,17 +15,20 @@ pub enum ParseError {
     Error {
         message: String,
         span: Span,
+        note: Option<String>,
     },
 
     ScanError {
         error: ScanError,
         span: Span,
+        note: Option<String>,
     },
 
     UnexpectedToken {
         message: String,
         offending: Token,
         expected: Option<TokenKind>,
+        note: Option<String>,
     },
 }
 
@@ -32,22 +36,23 @@ impl Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use ParseError::*;
         match self {
-            Error { message, span } => {
+            Error { message, span, note } => {
                 writeln!(f, "{}", message)?;
                 write!(f, "    At position {}", span)?;
-                Ok(())
+                write_note(f, note)
             }
 
-            ScanError { error, span } => {
+            ScanError { error, span, note } => {
                 writeln!(f, "{}", error)?;
                 write!(f, "    At position {}", span)?;
-                Ok(())
+                write_note(f, note)
             }
 
             UnexpectedToken {
                 message,
                 offending,
                 expected,
+                note,
             } => {
                 writeln!(f, "{}", message)?;
                 write!(
@@ -58,12 +63,19 @@ impl Display for ParseError {
                 if let Some(expected) = expected {
                     write!(f, "\n    Expected token `{}`", expected)?;
                 }
-                Ok(())
+                write_note(f, note)
             }
         }
     }
 }
 
+fn write_note(f: &mut fmt::Formatter<'_>, note: &Option<String>) -> fmt::Result {
+    match note {
+        Some(note) => write!(f, "\n    Note: {}", note),
+        None => Ok(()),
+    }
+}
+
 impl Error for ParseError {}
 
 impl ParseError {
@@ -76,4 +88,74 @@ impl ParseError {
             _ => false,
         }
     }
+
+    pub fn primary_span(&self) -> Span {
+        use ParseError::*;
+        match self {
+            Error { span, .. } | ScanError { span, .. } => *span,
+            UnexpectedToken { offending, .. } => offending.span,
+        }
+    }
+
+    /// Attaches a note describing where parsing resumed after this error's panic-mode recovery,
+    /// so both the `Display` impl and the rendered `Diagnostic` can tell the reader where the
+    /// repaired `Dummy` statement's contents were discarded up to.
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        use ParseError::*;
+        let slot = match &mut self {
+            Error { note, .. } | ScanError { note, .. } | UnexpectedToken { note, .. } => note,
+        };
+        *slot = Some(note.into());
+        self
+    }
+}
+
+impl From<&ParseError> for Diagnostic {
+    fn from(error: &ParseError) -> Self {
+        use ParseError::*;
+        let primary = error.primary_span();
+        let message = match error {
+            Error { message, .. } => message.clone(),
+            ScanError { error, .. } => error.to_string(),
+            UnexpectedToken {
+                message, expected, ..
+            } => match expected {
+                Some(expected) => format!("{} (expected `{}`)", message, expected),
+                None => message.clone(),
+            },
+        };
+        match note_of(error) {
+            Some(note) => Diagnostic::error(primary, format!("{} ({})", message, note)),
+            None => Diagnostic::error(primary, message),
+        }
+    }
+}
+
+fn note_of(error: &ParseError) -> Option<&String> {
+    use ParseError::*;
+    match error {
+        Error { note, .. } | ScanError { note, .. } | UnexpectedToken { note, .. } => note.as_ref(),
+    }
+}
+
+/// A sink for the `ParseError`s a `Parser` produces. `Parser` is generic over this trait (see
+/// `Parser::with_reporter`) so a caller can plug in something other than "buffer everything into a
+/// `Vec` and inspect it once parsing is done" — e.g. logging each error to stderr as it happens.
+pub trait ErrorReporter {
+    fn report(&mut self, error: ParseError);
+}
+
+/// The default reporter, used by `Parser::new`: simply buffers every error for the caller to
+/// inspect once parsing finishes, which is what `ParserOutcome` has always assumed.
+impl ErrorReporter for Vec<ParseError> {
+    fn report(&mut self, error: ParseError) {
+        self.push(error);
+    }
+}
+
+/// Lets any closure double as a reporter, e.g. `Parser::with_reporter(src, |e| eprintln!("{}", e))`.
+impl<F: FnMut(ParseError)> ErrorReporter for F {
+    fn report(&mut self, error: ParseError) {
+        self(error);
+    }
 }
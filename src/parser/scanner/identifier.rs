@@ -0,0 +1,48 @@
+use crate::token::TokenKind;
+
+/// Checks if the given char is valid as an identifier's start character.
+#[inline]
+pub fn is_valid_identifier_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+/// Checks if the given char can belong to an identifier's tail.
+#[inline]
+pub fn is_valid_identifier_tail(c: char) -> bool {
+    c.is_ascii_digit() || is_valid_identifier_start(c)
+}
+
+/// Checks if the given char is a valid digit for the given radix (2, 8, 10 or 16), as used by
+/// `0b`/`0o`/`0x` prefixed number literals.
+#[inline]
+pub fn is_digit_in_radix(c: char, radix: u32) -> bool {
+    c.is_digit(radix)
+}
+
+/// Returns the keyword `TokenKind` for the given identifier string, if any.
+pub fn keyword_from_str(ident: &str) -> Option<TokenKind> {
+    use TokenKind::*;
+    Some(match ident {
+        "nil" => Nil,
+        "true" => True,
+        "false" => False,
+        "this" => This,
+        "super" => Super,
+        "class" => Class,
+        "and" => And,
+        "or" => Or,
+        "if" => If,
+        "else" => Else,
+        "return" => Return,
+        "fun" => Fun,
+        "for" => For,
+        "while" => While,
+        "break" => Break,
+        "continue" => Continue,
+        "var" => Var,
+        "print" => Print,
+        "typeof" => Typeof,
+        "show" => Show,
+        _ => return None,
+    })
+}
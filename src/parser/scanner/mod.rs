@@ -0,0 +1,380 @@
+use std::str::Chars;
+
+use crate::{
+    parser::scanner::{
+        error::ScanError,
+        identifier::{
+            is_digit_in_radix, is_valid_identifier_start, is_valid_identifier_tail,
+            keyword_from_str,
+        },
+    },
+    span::Span,
+    token::{Token, TokenKind},
+};
+
+pub mod error;
+pub mod identifier;
+
+/// Converts a Lox source string into a stream of `Token`s.
+pub struct Scanner<'s> {
+    source: &'s str,
+    chars: Chars<'s>,
+    lexme_start: usize,
+    lexme_end: usize,
+    done: bool,
+}
+
+impl<'s> Scanner<'s> {
+    /// Creates a new scanner over the given source string.
+    pub fn new(source: &'s str) -> Self {
+        Scanner {
+            source,
+            chars: source.chars(),
+            lexme_start: 0,
+            lexme_end: 0,
+            done: false,
+        }
+    }
+}
+
+// Core implementation.
+impl Scanner<'_> {
+    fn scan_token(&mut self) -> Token {
+        if let Err(error) = self.skip_ignored() {
+            let span = Span::new(self.lexme_start, self.lexme_end);
+            return Token::new(TokenKind::Error(error), span);
+        }
+        self.lexme_start = self.lexme_end;
+
+        let kind = self.scan_kind();
+        let span = Span::new(self.lexme_start, self.lexme_end);
+        Token::new(kind, span)
+    }
+
+    fn scan_kind(&mut self) -> TokenKind {
+        use TokenKind::*;
+        let c = self.bump();
+
+        match c {
+            '\0' => Eof,
+
+            '(' => LeftParen,
+            ')' => RightParen,
+            '{' => LeftBrace,
+            '}' => RightBrace,
+            '.' => Dot,
+            ',' => Comma,
+            ';' => Semicolon,
+            '+' => Plus,
+            '-' => Minus,
+            '*' => Star,
+            '/' => Slash,
+
+            '=' => self.peek_select('=', EqualEqual, Equal),
+            '!' => self.peek_select('=', BangEqual, Bang),
+            '<' => self.peek_select('=', LessEqual, Less),
+            '>' => self.peek_select('=', GreaterEqual, Greater),
+
+            '|' if self.peek_first() == '>' => {
+                self.bump();
+                Pipe
+            }
+
+            '"' => self.string(),
+
+            c if c.is_ascii_digit() => self.number(),
+
+            c if is_valid_identifier_start(c) => self.identifier(),
+
+            unexpected => Error(ScanError::UnexpectedChar(unexpected)),
+        }
+    }
+
+    fn string(&mut self) -> TokenKind {
+        let mut value = String::new();
+        loop {
+            match self.peek_first() {
+                '"' => break,
+                _ if self.is_at_end() => return TokenKind::Error(ScanError::UnterminatedString),
+                '\\' => {
+                    self.bump(); // The `\`.
+                    match self.escape_sequence() {
+                        Ok(c) => value.push(c),
+                        Err(error) => return TokenKind::Error(error),
+                    }
+                }
+                c => {
+                    self.bump();
+                    value.push(c);
+                }
+            }
+        }
+        self.bump(); // The closing `"`.
+        TokenKind::String(value.as_str().into())
+    }
+
+    /// Decodes the escape sequence following a `\` already consumed by the caller:
+    /// `\n`, `\t`, `\\`, `\"` and `\u{XXXX}`.
+    fn escape_sequence(&mut self) -> Result<char, ScanError> {
+        match self.bump() {
+            'n' => Ok('\n'),
+            't' => Ok('\t'),
+            '\\' => Ok('\\'),
+            '"' => Ok('"'),
+            'u' => self.unicode_escape(),
+            other => Err(ScanError::InvalidEscapeSequence(other)),
+        }
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, ScanError> {
+        if self.peek_first() != '{' {
+            return Err(ScanError::InvalidUnicodeEscape);
+        }
+        self.bump(); // The `{`.
+
+        let mut digits = String::new();
+        while self.peek_first() != '}' {
+            if self.is_at_end() {
+                return Err(ScanError::InvalidUnicodeEscape);
+            }
+            digits.push(self.bump());
+        }
+        self.bump(); // The `}`.
+
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or(ScanError::InvalidUnicodeEscape)
+    }
+
+    /// Scans a number literal, called right after its first digit (always `0`-`9`) has already
+    /// been consumed by `scan_kind`. Handles plain decimals (with an optional fraction and
+    /// exponent) as well as `0x`/`0o`/`0b` prefixed integers, and allows `_` as a digit-group
+    /// separator anywhere in the digits.
+    fn number(&mut self) -> TokenKind {
+        if self.slice(0, 0) == "0" {
+            match self.peek_first() {
+                'x' => return self.radix_number(16),
+                'o' => return self.radix_number(8),
+                'b' => return self.radix_number(2),
+                _ => {}
+            }
+        }
+        self.decimal_number()
+    }
+
+    fn radix_number(&mut self, radix: u32) -> TokenKind {
+        self.bump(); // The `x`/`o`/`b` prefix letter.
+
+        let body_start = self.lexme_end;
+        while is_digit_in_radix(self.peek_first(), radix) || self.peek_first() == '_' {
+            self.bump();
+        }
+        let digits = self.digits_without_underscores(body_start, self.lexme_end);
+
+        if digits.is_empty() {
+            return TokenKind::Error(ScanError::InvalidNumberLiteral);
+        }
+        match i64::from_str_radix(&digits, radix) {
+            Ok(number) => TokenKind::Number(number as f64),
+            Err(_) => TokenKind::Error(ScanError::InvalidNumberLiteral),
+        }
+    }
+
+    fn decimal_number(&mut self) -> TokenKind {
+        self.consume_digits();
+
+        if self.peek_first() == '.' && self.peek_second().is_ascii_digit() {
+            self.bump(); // The `.`.
+            self.consume_digits();
+        }
+
+        if matches!(self.peek_first(), 'e' | 'E') && self.exponent_has_digits() {
+            self.bump(); // The `e`/`E`.
+            if matches!(self.peek_first(), '+' | '-') {
+                self.bump(); // The sign.
+            }
+            if !self.peek_first().is_ascii_digit() {
+                return TokenKind::Error(ScanError::InvalidNumberLiteral);
+            }
+            self.consume_digits();
+        }
+
+        let text = self.digits_without_underscores(self.lexme_start, self.lexme_end);
+        match text.parse() {
+            Ok(number) => TokenKind::Number(number),
+            Err(_) => TokenKind::Error(ScanError::InvalidNumberLiteral),
+        }
+    }
+
+    /// Consumes a run of ASCII digits, allowing `_` separators anywhere in between.
+    fn consume_digits(&mut self) {
+        while self.peek_first().is_ascii_digit() || self.peek_first() == '_' {
+            self.bump();
+        }
+    }
+
+    /// Checks whether an `e`/`E` at the current position is followed by an optional sign and at
+    /// least one digit, without consuming anything; used to tell an exponent from a bare
+    /// trailing `e` (e.g. an identifier starting right after a number).
+    fn exponent_has_digits(&self) -> bool {
+        let mut lookahead = self.chars.clone();
+        lookahead.next(); // The `e`/`E`.
+        let mut next = lookahead.next();
+        if matches!(next, Some('+') | Some('-')) {
+            next = lookahead.next();
+        }
+        matches!(next, Some(c) if c.is_ascii_digit())
+    }
+
+    fn digits_without_underscores(&self, start: usize, end: usize) -> String {
+        self.source[start..end].chars().filter(|&c| c != '_').collect()
+    }
+
+    fn identifier(&mut self) -> TokenKind {
+        while is_valid_identifier_tail(self.peek_first()) {
+            self.bump();
+        }
+        let lexme = self.slice(0, 0);
+        keyword_from_str(lexme).unwrap_or_else(|| TokenKind::Identifier(lexme.into()))
+    }
+
+    /// Advances *until* the next token shouldn't be ignored by the scanner (whitespace, a line
+    /// comment or a nested block comment).
+    fn skip_ignored(&mut self) -> Result<(), ScanError> {
+        loop {
+            match self.peek_first() {
+                '/' if self.peek_second() == '/' => {
+                    while self.peek_first() != '\n' && !self.is_at_end() {
+                        self.bump();
+                    }
+                }
+                '/' if self.peek_second() == '*' => {
+                    self.lexme_start = self.lexme_end;
+                    self.block_comment()?;
+                }
+                c if c.is_ascii_whitespace() => {
+                    self.bump();
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Skips a `/* ... */` comment, already positioned at its opening `/`, tracking nesting so
+    /// that `/* /* */ */` is a single comment. Errors if EOF is reached before every `/*` has a
+    /// matching `*/`.
+    fn block_comment(&mut self) -> Result<(), ScanError> {
+        self.bump(); // The `/`.
+        self.bump(); // The `*`.
+
+        let mut depth = 1;
+        while depth > 0 {
+            if self.is_at_end() {
+                return Err(ScanError::UnterminatedBlockComment);
+            }
+            match (self.peek_first(), self.peek_second()) {
+                ('/', '*') => {
+                    self.bump();
+                    self.bump();
+                    depth += 1;
+                }
+                ('*', '/') => {
+                    self.bump();
+                    self.bump();
+                    depth -= 1;
+                }
+                _ => {
+                    self.bump();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Helper methods.
+impl Scanner<'_> {
+    fn bump(&mut self) -> char {
+        match self.chars.next() {
+            Some(c) => {
+                self.lexme_end += c.len_utf8();
+                c
+            }
+            None => {
+                self.done = true;
+                EOF_CHAR
+            }
+        }
+    }
+
+    fn peek_first(&self) -> char {
+        self.chars.clone().next().unwrap_or(EOF_CHAR)
+    }
+
+    fn peek_second(&self) -> char {
+        let mut iter = self.chars.clone();
+        iter.next();
+        iter.next().unwrap_or(EOF_CHAR)
+    }
+
+    fn peek_select<T>(&mut self, expected: char, a: T, b: T) -> T {
+        if self.peek_first() == expected {
+            self.bump();
+            a
+        } else {
+            b
+        }
+    }
+
+    /// Returns a slice over the current lexme bounds, trimming `left` chars from the start and
+    /// `right` chars from the end.
+    fn slice(&self, left: usize, right: usize) -> &str {
+        &self.source[(self.lexme_start + left)..(self.lexme_end - right)]
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.chars.as_str().is_empty()
+    }
+}
+
+impl Iterator for Scanner<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let token = self.scan_token();
+        if token.kind == TokenKind::Eof {
+            self.done = true;
+        }
+        Some(token)
+    }
+}
+
+impl Scanner<'_> {
+    /// Scans `source` fully and reports whether it forms a complete unit, or why it doesn't
+    /// (e.g. a string left open all the way to EOF), so a REPL can decide whether to print a
+    /// continuation prompt and append the next line instead of surfacing a hard error.
+    pub fn scan_status(source: &str) -> ScanStatus {
+        for token in Scanner::new(source) {
+            if let TokenKind::Error(error) = token.kind {
+                if error.allows_continuation() {
+                    return ScanStatus::Incomplete(error);
+                }
+            }
+        }
+        ScanStatus::Complete
+    }
+}
+
+/// Whether a source string scans to a usable token stream or is missing its tail, as reported by
+/// [`Scanner::scan_status`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScanStatus {
+    Complete,
+    Incomplete(ScanError),
+}
+
+const EOF_CHAR: char = '\0';
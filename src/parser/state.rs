@@ -0,0 +1,55 @@
+/// Parser-wide options that alter parsing behavior without changing the grammar itself.
+#[derive(Debug, Clone, Default)]
+pub struct ParserOptions {
+    /// When set, the parser is lenient towards a few REPL-only conveniences, such as turning a
+    /// trailing expression statement (missing its `;`) into an implicit debug `print`.
+    pub repl_mode: bool,
+
+    /// When set, `if`/`while` conditions may be written without the otherwise-mandatory
+    /// parentheses (e.g. `if x > 0 { ... }`). See `Restrictions::NO_BLOCK_AS_EXPR`.
+    pub paren_free_conditions: bool,
+}
+
+/// A bitflag of contextual restrictions threaded through expression parsing, mirroring the
+/// technique rustc's parser uses (e.g. `Restrictions::NO_STRUCT_LITERAL`) to disambiguate a
+/// construct that would otherwise be grammatically ambiguous in a particular position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Restrictions(u8);
+
+impl Restrictions {
+    pub const NONE: Restrictions = Restrictions(0);
+
+    /// Set while parsing an `if`/`while` condition under `ParserOptions::paren_free_conditions`:
+    /// forbids any primary expression production that would otherwise be free to greedily
+    /// consume the `{` that actually opens the following statement block. Cleared inside an
+    /// explicit parenthesized group, where a `{` can never be ambiguous with the block that
+    /// follows the group.
+    ///
+    /// Nothing in this grammar currently parses a bare `{` as part of an expression, so this
+    /// restriction has no rejecting effect yet — but the flag is threaded correctly now, so it
+    /// will matter the moment such a construct (e.g. a block expression) is added.
+    pub const NO_BLOCK_AS_EXPR: Restrictions = Restrictions(1 << 0);
+
+    /// Also set while parsing an `if`/`while` condition under `ParserOptions::paren_free_conditions`,
+    /// mirroring rustc's `NO_STRUCT_LITERAL`: forbids any primary expression production that would
+    /// otherwise read a condition-position construct as a literal introduced by `(`/`{` rather
+    /// than as the group/block that actually follows the condition. Like `NO_BLOCK_AS_EXPR`,
+    /// nothing in this grammar parses such a construct yet, so this has no rejecting effect yet
+    /// either — it's threaded so it's ready the moment one is added. Cleared the same way, inside
+    /// an explicit parenthesized group.
+    pub const NO_GROUP_LITERAL: Restrictions = Restrictions(1 << 1);
+
+    pub fn contains(self, other: Restrictions) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn union(self, other: Restrictions) -> Restrictions {
+        Restrictions(self.0 | other.0)
+    }
+}
+
+impl Default for Restrictions {
+    fn default() -> Self {
+        Restrictions::NONE
+    }
+}
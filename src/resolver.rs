@@ -1,5 +1,5 @@
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, HashSet},
     mem,
 };
 
@@ -9,22 +9,49 @@ use crate::{
         stmt::{self, Stmt, StmtKind},
     },
     data::LoxIdent,
+    diagnostics::Diagnostic,
     interpreter::Interpreter,
     span::Span,
+    symbol::Symbol,
 };
 
 #[derive(Debug)]
 pub struct Resolver<'i> {
     interpreter: &'i mut Interpreter,
     state: ResolverState,
-    scopes: Vec<HashMap<String, BindingState>>,
+    scopes: Vec<HashMap<Symbol, ScopeEntry>>,
+    /// Names known to exist as globals: pre-registered builtins (passed in at construction) plus
+    /// every top-level `var`/`fun`/`class` declared anywhere in the program, collected by
+    /// `resolve` before it walks the tree. A `Var`/`Assignment` that resolves to neither a local
+    /// scope nor this set names a genuinely undefined global.
+    globals: HashSet<Symbol>,
     errors: Vec<ResolveError>,
+    warnings: Vec<ResolveError>,
 }
 
 impl Resolver<'_> {
-    pub fn resolve(mut self, stmts: &[Stmt]) -> (bool, Vec<ResolveError>) {
+    /// Resolves the given program, returning whether it resolved without errors, the errors
+    /// themselves (hard failures, same as before), and separately any unused-local-variable
+    /// warnings, which don't block execution.
+    pub fn resolve(mut self, stmts: &[Stmt]) -> (bool, Vec<ResolveError>, Vec<ResolveError>) {
+        self.collect_top_level_globals(stmts);
         self.resolve_stmts(stmts);
-        (self.errors.is_empty(), self.errors)
+        (self.errors.is_empty(), self.errors, self.warnings)
+    }
+
+    /// Registers every top-level declaration's name as a known global. Lox only treats top-level
+    /// `var`/`fun`/`class` declarations as true globals, so this deliberately doesn't recurse into
+    /// nested blocks or function bodies — a local with the same name still shadows normally.
+    fn collect_top_level_globals(&mut self, stmts: &[Stmt]) {
+        for stmt in stmts {
+            let name = match &stmt.kind {
+                StmtKind::VarDecl(var) => var.name.name,
+                StmtKind::FunDecl(fun) => fun.name.name,
+                StmtKind::ClassDecl(class) => class.name.name,
+                _ => continue,
+            };
+            self.globals.insert(name);
+        }
     }
 
     //
@@ -53,17 +80,21 @@ impl Resolver<'_> {
                 self.declare(&class.name);
                 self.define(&class.name);
 
-                self.scoped(|this| {
-                    this.initialize("this");
-                    for method in &class.methods {
-                        let state = if method.name.name == "init" {
-                            FunctionState::Init
-                        } else {
-                            FunctionState::Method
-                        };
-                        this.resolve_function(method, state);
+                match &class.super_name {
+                    Some(super_name) if super_name.name == class.name.name => {
+                        self.error(super_name.span, "A class can't inherit from itself");
+                        self.resolve_class_body(class);
                     }
-                });
+                    Some(super_name) => {
+                        self.resolve_binding(super_name);
+                        self.state.class = ClassState::Subclass;
+                        self.scoped(|this| {
+                            this.initialize("super");
+                            this.resolve_class_body(class);
+                        });
+                    }
+                    None => self.resolve_class_body(class),
+                }
 
                 self.state.class = old_class_state;
             }
@@ -81,7 +112,9 @@ impl Resolver<'_> {
             }
             While(while_stmt) => {
                 self.resolve_expr(&while_stmt.cond);
+                self.state.loop_depth += 1;
                 self.resolve_stmt(&while_stmt.body);
+                self.state.loop_depth -= 1;
             }
             Return(return_stmt) => {
                 if self.state.function == FunctionState::None {
@@ -97,10 +130,29 @@ impl Resolver<'_> {
                     self.resolve_expr(value);
                 }
             }
+            Break(break_stmt) => {
+                if self.state.loop_depth == 0 {
+                    self.error(
+                        break_stmt.break_span,
+                        "Illegal break statement, can't use break outside of a loop",
+                    );
+                }
+            }
+            Continue(continue_stmt) => {
+                if self.state.loop_depth == 0 {
+                    self.error(
+                        continue_stmt.continue_span,
+                        "Illegal continue statement, can't use continue outside of a loop",
+                    );
+                }
+            }
             Print(print) => self.resolve_expr(&print.expr),
             Block(block) => self.scoped(|this| this.resolve_stmts(&block.stmts)),
             Expr(expr) => self.resolve_expr(&expr.expr),
-            Dummy(_) => unreachable!(),
+            // A `Dummy` stands in for a statement the parser already failed (and diagnosed) on;
+            // there's nothing left to resolve. The resolver runs even over a tree containing
+            // these so its own errors can be reported alongside parse errors in one pass.
+            Dummy(_) => (),
         }
     }
 
@@ -113,13 +165,13 @@ impl Resolver<'_> {
         match &expr.kind {
             Lit(_) => (),
             This(this) => {
-                if self.state.class != ClassState::Class {
+                if self.state.class == ClassState::None {
                     self.error(
                         expr.span,
                         "Illegal this expression, can't use this outside of a class",
                     );
                 }
-                self.resolve_binding(&this.name)
+                self.resolve_binding(&this.name);
             }
             Var(var) => {
                 if self.query(&var.name, BindingState::Declared) {
@@ -129,8 +181,20 @@ impl Resolver<'_> {
                     );
                     return;
                 }
-                self.resolve_binding(&var.name);
+                self.resolve_binding_or_undefined(&var.name);
             }
+            Super(sup) => match self.state.class {
+                ClassState::None => {
+                    self.error(expr.span, "Can't use 'super' outside of a class")
+                }
+                ClassState::Class => self.error(
+                    expr.span,
+                    "Can't use 'super' in a class with no superclass",
+                ),
+                ClassState::Subclass => {
+                    self.resolve_binding(&sup.super_ident);
+                }
+            },
             Group(group) => self.resolve_expr(&group.expr),
             Get(get) => {
                 // Since properties are looked up dynamically by the interpreter (in a similar
@@ -161,28 +225,62 @@ impl Resolver<'_> {
             }
             Assignment(assignment) => {
                 self.resolve_expr(&assignment.value);
-                self.resolve_binding(&assignment.name);
+                self.resolve_binding_or_undefined(&assignment.name);
             }
+            // An `Error` stands in for an expression the parser already failed (and diagnosed)
+            // on; there's nothing left to resolve. See the `Dummy` arm above for why the resolver
+            // still runs over trees containing these.
+            Error(_) => (),
         }
     }
 }
 
 impl<'i> Resolver<'i> {
-    pub fn new(interpreter: &'i mut Interpreter) -> Resolver<'i> {
+    /// Creates a resolver for `interpreter`, pre-registering `globals` (e.g. native function
+    /// names) as known globals so references to them don't trip the undefined-global check.
+    pub fn new(
+        interpreter: &'i mut Interpreter,
+        globals: impl IntoIterator<Item = Symbol>,
+    ) -> Resolver<'i> {
         Self {
             interpreter,
             state: ResolverState::default(),
             scopes: Vec::new(),
+            globals: globals.into_iter().collect(),
             errors: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
+    /// Declares `ident` in the innermost scope, assigning it the next free slot in that scope
+    /// (i.e. the scope's current entry count, since slots are handed out in declaration order and
+    /// entries are never removed before the whole scope is popped) and recording that slot against
+    /// `ident`'s `AstId` so the interpreter can find it again once it defines the binding.
     fn declare(&mut self, ident: &LoxIdent) {
         if let Some(top) = self.scopes.last_mut() {
-            let entry = top.entry(ident.name.clone());
-            match entry {
+            let slot = top.len();
+            match top.entry(ident.name) {
+                Entry::Vacant(entry) => {
+                    entry.insert(ScopeEntry::new(ident.span, slot));
+                    self.interpreter.record_slot(ident.id, slot);
+                }
+                Entry::Occupied(_) => {
+                    self.error(ident.span, "Can't shadow a identifier in the same scope")
+                }
+            }
+        }
+    }
+
+    /// Like `declare`, but for function parameters, which are exempt from the unused-local
+    /// warning `scoped` emits (a parameter a method ignores isn't noteworthy the way a genuinely
+    /// dead local is).
+    fn declare_param(&mut self, ident: &LoxIdent) {
+        if let Some(top) = self.scopes.last_mut() {
+            let slot = top.len();
+            match top.entry(ident.name) {
                 Entry::Vacant(entry) => {
-                    entry.insert(BindingState::Declared);
+                    entry.insert(ScopeEntry::new(ident.span, slot).exempt());
+                    self.interpreter.record_slot(ident.id, slot);
                 }
                 Entry::Occupied(_) => {
                     self.error(ident.span, "Can't shadow a identifier in the same scope")
@@ -194,7 +292,7 @@ impl<'i> Resolver<'i> {
     fn define(&mut self, ident: &LoxIdent) {
         if let Some(top) = self.scopes.last_mut() {
             match top.get_mut(&ident.name) {
-                Some(binding) => *binding = BindingState::Initialized,
+                Some(entry) => entry.state = BindingState::Initialized,
                 None => {
                     self.error(
                         ident.span,
@@ -205,38 +303,90 @@ impl<'i> Resolver<'i> {
         }
     }
 
-    fn initialize(&mut self, ident: impl Into<String>) {
-        self.scopes
-            .last_mut()
-            .unwrap()
-            .insert(ident.into(), BindingState::Initialized);
+    /// Initializes a synthetic binding (`this`/`super`) that isn't declared through regular
+    /// source syntax, so it has no meaningful span and is exempt from the unused-local warning.
+    /// Always the sole entry of the scope it's inserted into (see `resolve_class_body`), so it's
+    /// always slot 0 there; there's no source `LoxIdent` to record that slot against either.
+    fn initialize(&mut self, name: &str) {
+        let top = self.scopes.last_mut().unwrap();
+        let slot = top.len();
+        top.insert(name.into(), ScopeEntry::new(Span::new(0, 0), slot).exempt());
     }
 
     fn query(&mut self, ident: &LoxIdent, expected: BindingState) -> bool {
-        self.scopes.last().and_then(|scope| scope.get(&ident.name)) == Some(&expected)
+        self.scopes
+            .last()
+            .and_then(|scope| scope.get(&ident.name))
+            .map(|entry| entry.state)
+            == Some(expected)
     }
 
-    fn resolve_binding(&mut self, ident: &LoxIdent) {
-        for (depth, scope) in self.scopes.iter().rev().enumerate() {
-            if scope.contains_key(&ident.name) {
-                self.interpreter.resolve_local(ident, depth);
-                return;
+    /// Tries to resolve `ident` to an enclosing lexical scope, returning whether it found one.
+    /// `Var`/`Assignment` use the return value to flag a genuinely undefined global; `This`/`Super`
+    /// ignore it, since an illegal use of either already gets a more specific error.
+    fn resolve_binding(&mut self, ident: &LoxIdent) -> bool {
+        for (depth, scope) in self.scopes.iter_mut().rev().enumerate() {
+            if let Some(entry) = scope.get_mut(&ident.name) {
+                entry.used = true;
+                self.interpreter.resolve_local(ident, depth, entry.slot);
+                return true;
             }
         }
+        false
+    }
+
+    /// Like `resolve_binding`, but for reads that should be rejected outright when the name isn't
+    /// bound locally and isn't a known global either (a builtin or a top-level declaration).
+    fn resolve_binding_or_undefined(&mut self, ident: &LoxIdent) {
+        if !self.resolve_binding(ident) && !self.globals.contains(&ident.name) {
+            self.error(ident.span, format!("Undefined variable `{}`", ident.name));
+        }
+    }
+
+    /// Resolves a class' `this`-scope and its methods. Shared between classes with and without a
+    /// superclass: the former just nests this scope one level deeper, inside a `super`-scope.
+    fn resolve_class_body(&mut self, class: &stmt::ClassDecl) {
+        // Static methods are resolved outside the `this`-scope below: they're dispatched on the
+        // class itself, not an instance, so a `this` used inside one is genuinely unbound.
+        for method in &class.methods {
+            if method.kind == stmt::MethodKind::Static {
+                self.resolve_function(method, FunctionState::Method);
+            }
+        }
+
+        self.scoped(|this| {
+            this.initialize("this");
+            for method in &class.methods {
+                if method.kind == stmt::MethodKind::Static {
+                    continue;
+                }
+                let state = if method.name.as_ref() == "init" {
+                    FunctionState::Init
+                } else {
+                    FunctionState::Method
+                };
+                this.resolve_function(method, state);
+            }
+        });
     }
 
     fn resolve_function(&mut self, decl: &stmt::FunDecl, state: FunctionState) {
         let old_function_state = mem::replace(&mut self.state.function, state);
+        // A function body starts a fresh loop nest: a `break`/`continue` textually inside an outer
+        // loop but inside this function is still illegal, since at runtime the call happens on its
+        // own `ControlFlow` propagation path, disconnected from that outer loop's `eval_while_stmt`.
+        let old_loop_depth = mem::replace(&mut self.state.loop_depth, 0);
 
         self.scoped(|this| {
             for param in &decl.params {
-                this.declare(param);
+                this.declare_param(param);
                 this.define(param);
             }
             this.resolve_stmts(&decl.body);
         });
 
         self.state.function = old_function_state;
+        self.state.loop_depth = old_loop_depth;
     }
 
     fn scoped<I>(&mut self, inner: I)
@@ -245,7 +395,15 @@ impl<'i> Resolver<'i> {
     {
         self.scopes.push(HashMap::new());
         let res = inner(self);
-        self.scopes.pop();
+        let scope = self.scopes.pop().unwrap();
+        for (name, entry) in scope {
+            if !entry.exempt && !entry.used {
+                self.warnings.push(ResolveError {
+                    span: entry.span,
+                    message: format!("Unused local variable `{}`", name),
+                });
+            }
+        }
         res
     }
 
@@ -259,6 +417,10 @@ impl<'i> Resolver<'i> {
 struct ResolverState {
     function: FunctionState,
     class: ClassState,
+    /// How many `while` loops (including desugared `for` loops) currently enclose the statement
+    /// being resolved. Reset to 0 on entry to a function body (see `resolve_function`). A `break`/
+    /// `continue` is only legal while this is non-zero.
+    loop_depth: usize,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -273,6 +435,7 @@ enum FunctionState {
 enum ClassState {
     None,
     Class,
+    Subclass,
 }
 
 macro_rules! impl_default_for_state {
@@ -295,8 +458,49 @@ enum BindingState {
     Initialized,
 }
 
+/// A scope's record for one binding: its lifecycle state plus the bookkeeping `scoped` needs to
+/// report unused locals when the scope is popped.
+#[derive(Debug)]
+struct ScopeEntry {
+    state: BindingState,
+    /// The declaration's span, used as the unused-local warning's primary span.
+    span: Span,
+    /// Flipped by `resolve_binding` the first time this binding is read or assigned to.
+    used: bool,
+    /// Set for bindings that shouldn't trigger the unused-local warning: function parameters and
+    /// synthetic `this`/`super` bindings.
+    exempt: bool,
+    /// This binding's index into its scope's `Environment`, assigned in declaration order by
+    /// `declare`/`declare_param`/`initialize`. Handed to the interpreter (via `resolve_local`) so
+    /// a use of this binding can index straight into the runtime scope instead of hashing its name.
+    slot: usize,
+}
+
+impl ScopeEntry {
+    fn new(span: Span, slot: usize) -> Self {
+        Self {
+            state: BindingState::Declared,
+            span,
+            used: false,
+            exempt: false,
+            slot,
+        }
+    }
+
+    fn exempt(mut self) -> Self {
+        self.exempt = true;
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct ResolveError {
     pub message: String,
     pub span: Span,
 }
+
+impl From<&ResolveError> for Diagnostic {
+    fn from(error: &ResolveError) -> Self {
+        Diagnostic::error(error.span, error.message.clone())
+    }
+}
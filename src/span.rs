@@ -60,3 +60,53 @@ impl Display for Span {
         }
     }
 }
+
+/// A 1-indexed (line, column) position, as a human would read it off a source listing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct LineCol {
+    pub line: usize,
+    pub col: usize,
+}
+
+impl Display for LineCol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.col)
+    }
+}
+
+/// Maps byte offsets into a source string to `LineCol` positions, so spans (which only carry
+/// byte offsets) can be rendered the way a human expects.
+#[derive(Debug, Clone)]
+pub struct LineMap {
+    /// Byte offset right after each `\n` in the source, i.e. the start of every line but the
+    /// first. Always sorted, which is what lets `locate` binary search it.
+    line_starts: Vec<usize>,
+}
+
+impl LineMap {
+    /// Builds a `LineMap` by scanning `source` once for newlines.
+    pub fn new(source: &str) -> Self {
+        let line_starts = source
+            .match_indices('\n')
+            .map(|(pos, _)| pos + 1)
+            .collect();
+        LineMap { line_starts }
+    }
+
+    /// Locates the 1-indexed line/column of the given byte offset.
+    pub fn locate(&self, offset: usize) -> LineCol {
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let line_start = if line == 0 { 0 } else { self.line_starts[line - 1] };
+        LineCol {
+            line: line + 1,
+            col: offset - line_start + 1,
+        }
+    }
+}
+
+impl Span {
+    /// Resolves this span's bounds to `(start, end)` `LineCol` positions.
+    pub fn resolve(&self, map: &LineMap) -> (LineCol, LineCol) {
+        (map.locate(self.lo), map.locate(self.hi))
+    }
+}
@@ -0,0 +1,72 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    sync::Mutex,
+};
+
+use lazy_static::lazy_static;
+
+/// A cheap, `Copy` handle to an interned string, handed out by the global interner.
+///
+/// Two `Symbol`s compare equal iff they were interned from equal strings, so comparisons and
+/// hashing (e.g. in `Resolver`'s scopes) collapse to comparing a `u32` instead of a `String`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Resolves this symbol back to the text it was interned from.
+    ///
+    /// The returned `&str` is valid for the whole program: interned strings are leaked, never
+    /// evicted.
+    pub fn resolve(self) -> &'static str {
+        INTERNER.lock().unwrap().resolve(self)
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.resolve())
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(string: &str) -> Self {
+        intern(string)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Interner {
+    map: HashMap<&'static str, Symbol>,
+    strings: Vec<&'static str>,
+}
+
+impl Interner {
+    fn intern(&mut self, string: &str) -> Symbol {
+        if let Some(&symbol) = self.map.get(string) {
+            return symbol;
+        }
+        let leaked: &'static str = Box::leak(string.to_owned().into_boxed_str());
+        let symbol = Symbol(self.strings.len() as u32);
+        self.strings.push(leaked);
+        self.map.insert(leaked, symbol);
+        symbol
+    }
+
+    fn resolve(&self, symbol: Symbol) -> &'static str {
+        self.strings[symbol.0 as usize]
+    }
+}
+
+// Yep, global state: a single process-wide symbol table, analogous to `ast::AstId`'s global
+// counter. Strings are leaked rather than reference-counted: a CLI/REPL run never interns enough
+// distinct identifiers for that to matter.
+lazy_static! {
+    static ref INTERNER: Mutex<Interner> = Mutex::new(Interner::default());
+}
+
+/// Interns `string`, returning a cheap handle to it. Interning the same text twice returns the
+/// same `Symbol`.
+pub fn intern(string: &str) -> Symbol {
+    INTERNER.lock().unwrap().intern(string)
+}
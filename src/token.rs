@@ -1,6 +1,6 @@
 use std::fmt::{self, Display};
 
-use crate::span::Span;
+use crate::{parser::scanner::error::ScanError, span::Span, symbol::Symbol};
 
 #[derive(Debug, Clone)]
 pub struct Token {
@@ -20,8 +20,8 @@ impl Token {
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum TokenKind {
-    Identifier(String),
-    String(String),
+    Identifier(Symbol),
+    String(Symbol),
     Number(f64),
 
     Comment(String),
@@ -46,6 +46,7 @@ pub enum TokenKind {
     LessEqual,
     Greater,
     GreaterEqual,
+    Pipe,
 
     Nil,
     True,
@@ -61,6 +62,8 @@ pub enum TokenKind {
     Fun,
     For,
     While,
+    Break,
+    Continue,
     Var,
     Print,
     Typeof,
@@ -69,7 +72,7 @@ pub enum TokenKind {
     Eof,
 
     Dummy,
-    Error(String),
+    Error(ScanError),
 }
 
 impl TokenKind {
@@ -78,12 +81,12 @@ impl TokenKind {
         // All tokens kinds patterns are checked in order to preserve match exhaustiveness.
         match self {
             Nil | True | False | This | Super | Class | And | Or | If | Else | Return | Fun
-            | For | While | Var | Print | Typeof | Show => true,
+            | For | While | Break | Continue | Var | Print | Typeof | Show => true,
 
             Identifier(_) | String(_) | Number(_) | Comment(_) | Whitespace(_) | LeftParen
             | RightParen | LeftBrace | RightBrace | Plus | Minus | Star | Slash | Dot | Comma
             | Semicolon | Bang | BangEqual | Equal | EqualEqual | Less | LessEqual | Greater
-            | GreaterEqual | Eof | Dummy | Error(_) => false,
+            | GreaterEqual | Pipe | Eof | Dummy | Error(_) => false,
         }
     }
 }
@@ -122,6 +125,7 @@ impl Display for TokenKind {
             LessEqual => f.write_str("<="),
             Greater => f.write_str(">"),
             GreaterEqual => f.write_str(">="),
+            Pipe => f.write_str("|>"),
             Nil => f.write_str("nil"),
             True => f.write_str("true"),
             False => f.write_str("false"),
@@ -136,6 +140,8 @@ impl Display for TokenKind {
             Fun => f.write_str("fun"),
             For => f.write_str("for"),
             While => f.write_str("while"),
+            Break => f.write_str("break"),
+            Continue => f.write_str("continue"),
             Var => f.write_str("var"),
             Print => f.write_str("print"),
             Typeof => f.write_str("typeof"),
@@ -0,0 +1,544 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    mem,
+};
+
+use crate::{
+    ast::{
+        expr::{self, Expr, ExprKind},
+        stmt::{self, Stmt, StmtKind},
+    },
+    data::LoxValue,
+    diagnostics::Diagnostic,
+    span::Span,
+    symbol::Symbol,
+    token::TokenKind,
+};
+
+/// A type in the checker's world: either a concrete Lox type or an as-yet-unresolved type
+/// variable, solved against `Typeck`'s substitution map by `unify`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ty {
+    Var(u32),
+    Num,
+    Bool,
+    Str,
+    Nil,
+    Fun(Vec<Ty>, Box<Ty>),
+}
+
+/// A let-bound type scheme: a type plus the variables within it that are free to be instantiated
+/// fresh at each use. Only `fun` declarations are generalized this way (let-polymorphism); `var`
+/// bindings stay monomorphic, since Lox lets them be reassigned.
+#[derive(Debug, Clone)]
+struct Scheme {
+    vars: Vec<u32>,
+    ty: Ty,
+}
+
+impl Scheme {
+    fn monomorphic(ty: Ty) -> Self {
+        Scheme { vars: Vec::new(), ty }
+    }
+}
+
+/// Hindley-Milner style type inference (Algorithm W), run between the `Resolver` and the
+/// `Interpreter` so that `typeof`/`show` (and every other expression) can be checked for
+/// consistency before the program actually runs.
+///
+/// Classes are not modeled structurally: `this`, `super`, and property get/set expressions all
+/// type as a fresh, never-constrained variable, since Lox's duck-typed objects don't fit a plain
+/// HM type without row polymorphism. That's a deliberately deferred piece of this pass, not an
+/// oversight.
+pub struct Typeck {
+    subst: HashMap<u32, Ty>,
+    next_var: u32,
+    scopes: Vec<HashMap<Symbol, Scheme>>,
+    /// The return type expected in the function currently being checked, if any.
+    return_ty: Option<Ty>,
+    errors: Vec<TypeError>,
+}
+
+type TResult<T> = Result<T, TypeError>;
+
+impl Typeck {
+    pub fn new() -> Self {
+        Typeck {
+            subst: HashMap::new(),
+            next_var: 0,
+            scopes: vec![HashMap::new()],
+            return_ty: None,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn check(mut self, stmts: &[Stmt]) -> (bool, Vec<TypeError>) {
+        for stmt in stmts {
+            if let Err(error) = self.check_stmt(stmt) {
+                self.errors.push(error);
+            }
+        }
+        (self.errors.is_empty(), self.errors)
+    }
+
+    //
+    // Statements
+    //
+
+    fn check_stmt(&mut self, stmt: &Stmt) -> TResult<()> {
+        use StmtKind::*;
+        match &stmt.kind {
+            VarDecl(var) => {
+                let ty = match &var.init {
+                    Some(init) => self.check_expr(init)?,
+                    None => Ty::Nil,
+                };
+                self.bind(var.name.name, Scheme::monomorphic(ty));
+                Ok(())
+            }
+            ClassDecl(class) => {
+                // See the note on `Typeck` above: methods are checked, but `this` inside them
+                // (and every property access) is left as an unconstrained type variable.
+                for method in &class.methods {
+                    self.check_function(method)?;
+                }
+                Ok(())
+            }
+            FunDecl(fun) => self.check_function(fun),
+            // Same truthiness rationale as `Unary::Bang`/`Logical`: `eval_if_stmt`/
+            // `eval_while_stmt` branch on `lox_is_truthy`, which accepts any value, so the
+            // condition isn't constrained to `Bool` here either.
+            If(if_stmt) => {
+                self.check_expr(&if_stmt.cond)?;
+                self.check_stmt(&if_stmt.then_branch)?;
+                if let Some(else_branch) = &if_stmt.else_branch {
+                    self.check_stmt(else_branch)?;
+                }
+                Ok(())
+            }
+            While(while_stmt) => {
+                self.check_expr(&while_stmt.cond)?;
+                self.check_stmt(&while_stmt.body)
+            }
+            Return(return_stmt) => {
+                let ty = match &return_stmt.value {
+                    Some(value) => self.check_expr(value)?,
+                    None => Ty::Nil,
+                };
+                // A `return` outside a function is already rejected by the `Resolver`, so if we
+                // get here with no `return_ty` in scope there's nothing left to check against.
+                if let Some(return_ty) = self.return_ty.clone() {
+                    self.unify(&ty, &return_ty, return_stmt.return_span)?;
+                }
+                Ok(())
+            }
+            Break(_) | Continue(_) => Ok(()),
+            Print(print) => self.check_expr(&print.expr).map(|_| ()),
+            Block(block) => self.scoped(|this| {
+                for stmt in &block.stmts {
+                    this.check_stmt(stmt)?;
+                }
+                Ok(())
+            }),
+            Expr(expr) => self.check_expr(&expr.expr).map(|_| ()),
+            Dummy(_) => Ok(()),
+        }
+    }
+
+    /// Checks a function declaration: binds its name to a `Fun` scheme (monomorphically, before
+    /// checking the body, so a recursive call inside resolves to the same, not-yet-generalized
+    /// type), checks the body against fresh parameter types and a fresh return type, then
+    /// generalizes the free variables in the inferred type into a scheme so each call site gets
+    /// its own fresh instantiation (let-polymorphism).
+    fn check_function(&mut self, decl: &stmt::FunDecl) -> TResult<()> {
+        // Snapshotted before anything about this function is bound, so generalizing its type
+        // later doesn't let the function's own fresh variables slip past the exclusion list.
+        let enclosing_free = self.env_free_vars();
+
+        let param_tys: Vec<Ty> = decl.params.iter().map(|_| self.fresh_var()).collect();
+        let ret_ty = self.fresh_var();
+        let fun_ty = Ty::Fun(param_tys.clone(), Box::new(ret_ty.clone()));
+        self.bind(decl.name.name, Scheme::monomorphic(fun_ty.clone()));
+
+        let outer_return_ty = mem::replace(&mut self.return_ty, Some(ret_ty));
+        let result = self.scoped(|this| {
+            for (param, ty) in decl.params.iter().zip(param_tys) {
+                this.bind(param.name, Scheme::monomorphic(ty));
+            }
+            for stmt in &decl.body {
+                this.check_stmt(stmt)?;
+            }
+            Ok(())
+        });
+        self.return_ty = outer_return_ty;
+        result?;
+
+        let scheme = self.generalize(fun_ty, &enclosing_free);
+        self.bind(decl.name.name, scheme);
+        Ok(())
+    }
+
+    /// Every type variable currently free in the enclosing environment (i.e. not already
+    /// generalized away by some scheme that binds it) — `generalize` must not quantify over any
+    /// of these, since Lox functions are real closures over their enclosing scope and doing so
+    /// would let unrelated call sites of an outer, still-monomorphic variable unify incompatible
+    /// types against each other.
+    fn env_free_vars(&self) -> Vec<u32> {
+        let mut vars = Vec::new();
+        for scope in &self.scopes {
+            for scheme in scope.values() {
+                let mut ty_vars = Vec::new();
+                Self::collect_vars(&self.resolve_deep(&scheme.ty), &mut ty_vars);
+                for var in ty_vars {
+                    if !scheme.vars.contains(&var) && !vars.contains(&var) {
+                        vars.push(var);
+                    }
+                }
+            }
+        }
+        vars
+    }
+
+    //
+    // Expressions
+    //
+
+    fn check_expr(&mut self, expr: &Expr) -> TResult<Ty> {
+        use ExprKind::*;
+        match &expr.kind {
+            Lit(lit) => Ok(match &lit.value {
+                LoxValue::Number(_) => Ty::Num,
+                LoxValue::String(_) => Ty::Str,
+                LoxValue::Boolean(_) => Ty::Bool,
+                LoxValue::Nil => Ty::Nil,
+                // Literals never actually evaluate to a function or object value.
+                LoxValue::Function(_) | LoxValue::Object(_) => self.fresh_var(),
+            }),
+            // Deferred, per the note on `Typeck`: dynamic by nature.
+            This(_) | Super(_) | Get(_) => Ok(self.fresh_var()),
+            Var(var) => Ok(self.instantiate(var.name.name)),
+            Group(group) => self.check_expr(&group.expr),
+            Set(set) => {
+                self.check_expr(&set.object)?;
+                self.check_expr(&set.value)
+            }
+            Call(call) => {
+                let callee_ty = self.check_expr(&call.callee)?;
+                let mut arg_tys = Vec::with_capacity(call.args.len());
+                for arg in &call.args {
+                    arg_tys.push(self.check_expr(arg)?);
+                }
+                let ret_ty = self.fresh_var();
+                self.unify(&callee_ty, &Ty::Fun(arg_tys, Box::new(ret_ty.clone())), expr.span)?;
+                Ok(ret_ty)
+            }
+            Unary(unary) => {
+                let operand_ty = self.check_expr(&unary.operand)?;
+                match &unary.operator.kind {
+                    TokenKind::Minus => {
+                        self.unify(&operand_ty, &Ty::Num, expr.span)?;
+                        Ok(Ty::Num)
+                    }
+                    // `!` follows Lox's truthiness rules (every value is truthy or falsy), so the
+                    // operand isn't constrained to `Bool`.
+                    TokenKind::Bang => Ok(Ty::Bool),
+                    TokenKind::Typeof | TokenKind::Show => Ok(Ty::Str),
+                    _ => unreachable!("Invalid unary operator ({:?})", unary.operator.kind),
+                }
+            }
+            // `|>` doesn't check its right-hand side as a plain expression like every other
+            // binary operator does (see `check_pipe_expr`), so it's special-cased before the
+            // generic `left_ty`/`right_ty` checks the rest of this arm relies on.
+            Binary(binary) if binary.operator.kind == TokenKind::Pipe => {
+                self.check_pipe_expr(binary, expr.span)
+            }
+            Binary(binary) => {
+                let left_ty = self.check_expr(&binary.left)?;
+                let right_ty = self.check_expr(&binary.right)?;
+                use TokenKind::*;
+                match &binary.operator.kind {
+                    Plus => {
+                        self.unify(&left_ty, &right_ty, expr.span)?;
+                        match self.resolve(&left_ty) {
+                            Ty::Num | Ty::Str | Ty::Var(_) => {}
+                            other => {
+                                return Err(TypeError {
+                                    span: expr.span,
+                                    message: format!(
+                                        "Binary `+` operator can only operate over two numbers \
+                                        or two strings, got `{}`",
+                                        other
+                                    ),
+                                })
+                            }
+                        }
+                        Ok(left_ty)
+                    }
+                    Minus | Star | Slash => {
+                        self.unify(&left_ty, &Ty::Num, expr.span)?;
+                        self.unify(&right_ty, &Ty::Num, expr.span)?;
+                        Ok(Ty::Num)
+                    }
+                    Greater | GreaterEqual | Less | LessEqual => {
+                        self.unify(&left_ty, &Ty::Num, expr.span)?;
+                        self.unify(&right_ty, &Ty::Num, expr.span)?;
+                        Ok(Ty::Bool)
+                    }
+                    EqualEqual | BangEqual => {
+                        self.unify(&left_ty, &right_ty, expr.span)?;
+                        Ok(Ty::Bool)
+                    }
+                    _ => unreachable!("Invalid binary operator ({:?})", binary.operator.kind),
+                }
+            }
+            // `and`/`or` follow Lox's truthy/falsy rules, same as `!` (see the `Unary::Bang`
+            // arm): `eval_logical_expr` short-circuits on truthiness and returns whichever
+            // operand's *value* decided the result, not a `Bool`, so e.g. `x or "default"` is a
+            // normal, valid Lox idiom. Neither operand is constrained here, beyond still being
+            // checked for their own internal errors; the result is deferred like `Get`/`This`.
+            Logical(logical) => {
+                self.check_expr(&logical.left)?;
+                self.check_expr(&logical.right)?;
+                Ok(self.fresh_var())
+            }
+            Assignment(assignment) => {
+                let value_ty = self.check_expr(&assignment.value)?;
+                let name_ty = self.instantiate(assignment.name.name);
+                self.unify(&name_ty, &value_ty, expr.span)?;
+                Ok(value_ty)
+            }
+            // Stands in for an expression the parser already failed (and diagnosed) on; give it
+            // a fresh, unconstrained type variable rather than asserting any particular type.
+            Error(_) => Ok(self.fresh_var()),
+        }
+    }
+
+    /// Checks `lhs |> rhs` the same way `Call` is checked, just with `lhs`'s type prepended to
+    /// the callee's expected argument types. When `rhs` is itself a call expression
+    /// (`x |> f(a, b)`), its callee is checked directly here (rather than deferring to the
+    /// `Call` arm above) so the unification sees all three arguments `f` is actually invoked
+    /// with at runtime, not just the two written at the call site.
+    fn check_pipe_expr(&mut self, binary: &expr::Binary, span: Span) -> TResult<Ty> {
+        let left_ty = self.check_expr(&binary.left)?;
+        let (callee_ty, mut arg_tys) = match &binary.right.kind {
+            ExprKind::Call(call) => {
+                let callee_ty = self.check_expr(&call.callee)?;
+                let mut arg_tys = Vec::with_capacity(call.args.len());
+                for arg in &call.args {
+                    arg_tys.push(self.check_expr(arg)?);
+                }
+                (callee_ty, arg_tys)
+            }
+            _ => (self.check_expr(&binary.right)?, Vec::new()),
+        };
+        arg_tys.insert(0, left_ty);
+        let ret_ty = self.fresh_var();
+        self.unify(&callee_ty, &Ty::Fun(arg_tys, Box::new(ret_ty.clone())), span)?;
+        Ok(ret_ty)
+    }
+
+    //
+    // Environment
+    //
+
+    fn bind(&mut self, name: Symbol, scheme: Scheme) {
+        self.scopes.last_mut().expect("at least one scope").insert(name, scheme);
+    }
+
+    /// Looks up `name`'s scheme and instantiates it: every one of its generalized variables is
+    /// replaced with a fresh one, so e.g. calling a generic `fun identity(x) { return x; }` twice
+    /// with different argument types doesn't unify those types together.
+    fn instantiate(&mut self, name: Symbol) -> Ty {
+        let scheme = self
+            .scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&name))
+            .cloned();
+        match scheme {
+            Some(scheme) => {
+                let mapping: HashMap<u32, Ty> =
+                    scheme.vars.iter().map(|&v| (v, self.fresh_var())).collect();
+                Self::substitute_vars(&scheme.ty, &mapping)
+            }
+            // An unbound name (e.g. a global defined by a native function, or a forward
+            // reference the resolver already treats as dynamic) just gets a fresh, unconstrained
+            // type rather than a hard error here; the `Resolver` is responsible for catching
+            // genuinely undefined bindings.
+            None => self.fresh_var(),
+        }
+    }
+
+    fn substitute_vars(ty: &Ty, mapping: &HashMap<u32, Ty>) -> Ty {
+        match ty {
+            Ty::Var(v) => mapping.get(v).cloned().unwrap_or_else(|| ty.clone()),
+            Ty::Fun(params, ret) => Ty::Fun(
+                params.iter().map(|p| Self::substitute_vars(p, mapping)).collect(),
+                Box::new(Self::substitute_vars(ret, mapping)),
+            ),
+            concrete => concrete.clone(),
+        }
+    }
+
+    /// Generalizes `ty`'s free variables (under the current substitution) into a `Scheme`, so
+    /// future uses each get their own fresh instantiation. Variables still free in an enclosing
+    /// scope are excluded, since generalizing those would let unrelated uses unify incompatible
+    /// types.
+    fn generalize(&self, ty: Ty, exclude: &[u32]) -> Scheme {
+        let ty = self.resolve_deep(&ty);
+        let mut vars = Vec::new();
+        Self::collect_vars(&ty, &mut vars);
+        vars.retain(|var| !exclude.contains(var));
+        Scheme { vars, ty }
+    }
+
+    fn collect_vars(ty: &Ty, out: &mut Vec<u32>) {
+        match ty {
+            Ty::Var(v) => {
+                if !out.contains(v) {
+                    out.push(*v);
+                }
+            }
+            Ty::Fun(params, ret) => {
+                for param in params {
+                    Self::collect_vars(param, out);
+                }
+                Self::collect_vars(ret, out);
+            }
+            _ => {}
+        }
+    }
+
+    fn scoped<T>(&mut self, inner: impl FnOnce(&mut Self) -> TResult<T>) -> TResult<T> {
+        self.scopes.push(HashMap::new());
+        let result = inner(self);
+        self.scopes.pop();
+        result
+    }
+
+    //
+    // Unification
+    //
+
+    fn fresh_var(&mut self) -> Ty {
+        let var = self.next_var;
+        self.next_var += 1;
+        Ty::Var(var)
+    }
+
+    /// Follows `ty` through the substitution map at the top level only, without descending into
+    /// `Fun` operand/return types (a resolved `Var` may itself resolve to another `Var`, so this
+    /// loops until it hits either an unbound variable or a concrete type).
+    fn resolve(&self, ty: &Ty) -> Ty {
+        let mut ty = ty.clone();
+        while let Ty::Var(v) = ty {
+            match self.subst.get(&v) {
+                Some(next) => ty = next.clone(),
+                None => break,
+            }
+        }
+        ty
+    }
+
+    /// Like `resolve`, but recurses into `Fun` operand/return types too.
+    fn resolve_deep(&self, ty: &Ty) -> Ty {
+        match self.resolve(ty) {
+            Ty::Fun(params, ret) => Ty::Fun(
+                params.iter().map(|p| self.resolve_deep(p)).collect(),
+                Box::new(self.resolve_deep(&ret)),
+            ),
+            resolved => resolved,
+        }
+    }
+
+    fn unify(&mut self, a: &Ty, b: &Ty, span: Span) -> TResult<()> {
+        let a = self.resolve(a);
+        let b = self.resolve(b);
+        match (&a, &b) {
+            (Ty::Var(v1), Ty::Var(v2)) if v1 == v2 => Ok(()),
+            (Ty::Var(v), other) | (other, Ty::Var(v)) => {
+                if Self::occurs(*v, other, &self.subst) {
+                    return Err(TypeError {
+                        span,
+                        message: format!("Infinite type: `{}` occurs in `{}`", Ty::Var(*v), other),
+                    });
+                }
+                self.subst.insert(*v, other.clone());
+                Ok(())
+            }
+            (Ty::Fun(params1, ret1), Ty::Fun(params2, ret2)) => {
+                if params1.len() != params2.len() {
+                    return Err(TypeError {
+                        span,
+                        message: format!(
+                            "Expected a function of {} argument(s), got one of {}",
+                            params1.len(),
+                            params2.len()
+                        ),
+                    });
+                }
+                for (p1, p2) in params1.iter().zip(params2) {
+                    self.unify(p1, p2, span)?;
+                }
+                self.unify(ret1, ret2, span)
+            }
+            (x, y) if x == y => Ok(()),
+            (x, y) => Err(TypeError {
+                span,
+                message: format!("Type mismatch: expected `{}`, got `{}`", x, y),
+            }),
+        }
+    }
+
+    fn occurs(var: u32, ty: &Ty, subst: &HashMap<u32, Ty>) -> bool {
+        match ty {
+            Ty::Var(v) if *v == var => true,
+            Ty::Var(v) => subst.get(v).map_or(false, |next| Self::occurs(var, next, subst)),
+            Ty::Fun(params, ret) => {
+                params.iter().any(|p| Self::occurs(var, p, subst)) || Self::occurs(var, ret, subst)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Default for Typeck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ty::Var(v) => write!(f, "t{}", v),
+            Ty::Num => f.write_str("number"),
+            Ty::Bool => f.write_str("boolean"),
+            Ty::Str => f.write_str("string"),
+            Ty::Nil => f.write_str("nil"),
+            Ty::Fun(params, ret) => {
+                f.write_str("fun(")?;
+                for (i, param) in params.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", param)?;
+                }
+                write!(f, ") -> {}", ret)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TypeError {
+    pub message: String,
+    pub span: Span,
+}
+
+impl From<&TypeError> for Diagnostic {
+    fn from(error: &TypeError) -> Self {
+        Diagnostic::error(error.span, error.message.clone())
+    }
+}
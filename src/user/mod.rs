@@ -1,54 +1,72 @@
 use std::{fs, io, path::Path};
 
 use crate::{
+    diagnostics::{self, Diagnostic},
     interpreter::Interpreter,
-    parser::{Parser, ParserOutcome},
+    parser::{state::ParserOptions, Parser, ParserOutcome},
     resolver::Resolver,
-    user::diagnostic_printer::print_span_window,
+    typeck::Typeck,
 };
 
-pub mod diagnostic_printer;
 pub mod repl;
 
 fn handle_parser_outcome(
     src: &str,
-    (stmts, errors): &ParserOutcome,
+    (stmts, parse_errors): &ParserOutcome,
     interpreter: &mut Interpreter,
 ) -> bool {
     let writer = &mut io::stderr();
 
-    // parser
-    if !errors.is_empty() {
-        for error in errors {
-            eprintln!("{}\n", error);
-            print_span_window(writer, src, error.primary_span());
-        }
+    // parser + resolver: the resolver runs unconditionally (even over a tree containing parse
+    // errors' synthesized `Stmt::Dummy` placeholders) so a user sees every parse *and* resolve
+    // problem in one run, rather than fixing parse errors one at a time before ever learning
+    // about a resolve error further down the same file.
+    let globals = interpreter.global_names();
+    let resolver = Resolver::new(interpreter, globals);
+    let (resolve_ok, resolve_errors, warnings) = resolver.resolve(stmts);
+
+    let mut diagnostics: Vec<Diagnostic> = parse_errors.iter().map(Diagnostic::from).collect();
+    diagnostics.extend(warnings.iter().map(|w| Diagnostic::warning(w.span, w.message.clone())));
+    if !parse_errors.is_empty() || !resolve_ok {
+        diagnostics.extend(resolve_errors.iter().map(Diagnostic::from));
+        diagnostics::render_all(writer, src, &mut diagnostics);
         return false;
     }
+    if !diagnostics.is_empty() {
+        diagnostics::render_all(writer, src, &mut diagnostics);
+    }
 
-    // resolver
-    let resolver = Resolver::new(interpreter);
-    let (ok, errors) = resolver.resolve(stmts);
+    // typeck
+    let (ok, errors) = Typeck::new().check(stmts);
     if !ok {
-        for error in errors {
-            eprintln!("{}; at position {}\n", error.message, error.span);
-            print_span_window(writer, src, error.span);
-        }
+        let mut diagnostics: Vec<Diagnostic> = errors.iter().map(Diagnostic::from).collect();
+        diagnostics::render_all(writer, src, &mut diagnostics);
         return false;
     }
 
     // interpreter
     if let Err(error) = interpreter.interpret(stmts) {
-        eprintln!("{}\n", error);
-        print_span_window(writer, src, error.primary_span());
+        diagnostics::render(writer, src, &Diagnostic::from(&error));
         return false;
     }
     true
 }
 
 pub fn run_file(file: impl AsRef<Path>, interpreter: Option<&mut Interpreter>) -> io::Result<bool> {
+    run_file_with_options(file, interpreter, ParserOptions::default())
+}
+
+/// Same as `run_file`, but with the `Parser` configured via `options` (e.g.
+/// `ParserOptions::paren_free_conditions`) instead of the defaults.
+pub fn run_file_with_options(
+    file: impl AsRef<Path>,
+    interpreter: Option<&mut Interpreter>,
+    options: ParserOptions,
+) -> io::Result<bool> {
     let src = &fs::read_to_string(file)?;
-    let outcome = Parser::new(src).parse();
+    let mut parser = Parser::new(src);
+    parser.options = options;
+    let outcome = parser.parse();
     let status = handle_parser_outcome(
         src,
         &outcome,
@@ -1,40 +1,84 @@
-use std::io::{self, Write};
+use std::{env, fmt, io, path::PathBuf, time::Instant};
+
+use rustyline::{error::ReadlineError, DefaultEditor};
 
 use crate::{
     ast,
+    diagnostics::{self, Diagnostic},
     interpreter::Interpreter,
     parser::{error::ParseError, Parser},
     user::{handle_parser_outcome, run_file},
+    vm::Vm,
 };
 
+/// Name of the persisted line-history file, loaded from (and saved back to) the user's home
+/// directory.
+const HISTORY_FILE_NAME: &str = ".rs_lox_history";
+
+/// Which engine evaluates the REPL's input. Both backends share the same `:lex`/`:ast` debug
+/// views (those only ever go through the scanner and the tree-walk `Parser`; the VM's own
+/// `Compiler` reads the scanner's tokens directly, without that `Parser`), so switching backends
+/// only changes which one actually runs the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    Tree,
+    Vm,
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Backend::Tree => write!(f, "tree"),
+            Backend::Vm => write!(f, "vm"),
+        }
+    }
+}
+
 pub struct Repl {
     interpreter: Interpreter,
+    vm: Vm,
+    backend: Backend,
+    editor: DefaultEditor,
     current_src: String,
     show_lex: bool,
     show_ast: bool,
+    show_time: bool,
+    paren_free_conditions: bool,
     done: bool,
 }
 
 impl Repl {
     pub fn run() -> io::Result<()> {
-        Self::new().start()
+        Self::new()?.start()
     }
 
-    fn new() -> Self {
-        Self {
+    fn new() -> io::Result<Self> {
+        let mut editor = DefaultEditor::new().map_err(to_io_error)?;
+        if let Some(path) = history_path() {
+            // A missing history file (e.g. the very first run) is expected, not an error; any
+            // other load failure is likewise swallowed, since a REPL shouldn't refuse to start
+            // just because its history can't be recovered.
+            let _ = editor.load_history(&path);
+        }
+        Ok(Self {
             interpreter: Interpreter::new(),
+            vm: Vm::new(),
+            backend: Backend::Tree,
+            editor,
             current_src: "".into(),
             show_lex: false,
             show_ast: false,
+            show_time: false,
+            paren_free_conditions: false,
             done: false,
-        }
+        })
     }
 
     fn start(mut self) -> io::Result<()> {
         eprintln!("Welcome to rs-lox. Enter Ctrl+D or `:exit` to exit.\n");
 
         while !self.done {
-            let (line, is_eof) = self.read_line()?;
+            let (line, is_eof) = self.read_line();
 
             // If previous line started with `:`, interpret it as a command and
             // skip this iteration entirely, handling the command.
@@ -48,6 +92,7 @@ impl Repl {
 
             let mut parser = Parser::new(&self.current_src);
             parser.options.repl_mode = true;
+            parser.options.paren_free_conditions = self.paren_free_conditions;
             let outcome @ (stmts, errors) = &parser.parse();
 
             // If the parser produced an error, but the error allows REPL continuation then we
@@ -65,36 +110,84 @@ impl Repl {
                 ast::dbg::print_program_tree(stmts);
             }
 
-            handle_parser_outcome(&self.current_src, outcome, &mut self.interpreter);
+            let start = self.show_time.then(Instant::now);
+            match self.backend {
+                Backend::Tree => {
+                    handle_parser_outcome(&self.current_src, outcome, &mut self.interpreter);
+                }
+                Backend::Vm => self.eval_vm(errors),
+            }
+            if let Some(start) = start {
+                eprintln!("[{} backend: {:?}]", self.backend, start.elapsed());
+            }
             self.current_src = "".into();
         }
+
+        if let Some(path) = history_path() {
+            let _ = self.editor.save_history(&path);
+        }
         Ok(())
     }
 
-    fn read_line(&mut self) -> io::Result<(String, bool)> {
+    /// Reads one line via `rustyline`, which gives the REPL cursor movement, history recall, and
+    /// `Ctrl+R` reverse search on top of the accumulating `>>>`/`...` prompt this REPL has always
+    /// shown. Returns the line with its trailing newline restored (so it still concatenates into
+    /// `current_src` the same way a raw `stdin().read_line()` result used to) and whether input
+    /// has ended.
+    fn read_line(&mut self) -> (String, bool) {
         let prompt = if self.current_src.is_empty() {
-            ">>>"
+            ">>> "
         } else {
-            "..."
+            "... "
         };
-        print!("{} ", prompt);
-        io::stdout().flush()?;
-
-        let mut line = String::new();
-        let is_eof = io::stdin().read_line(&mut line)? == 0;
-        self.done = is_eof && self.current_src.is_empty();
-
-        if is_eof {
-            println!();
+        match self.editor.readline(prompt) {
+            Ok(line) => {
+                let _ = self.editor.add_history_entry(line.as_str());
+                (line + "\n", false)
+            }
+            // Ctrl+C abandons the statement being accumulated (if any) rather than exiting, the
+            // same way most line-editing REPLs treat an interrupt.
+            Err(ReadlineError::Interrupted) => {
+                self.current_src.clear();
+                ("".into(), false)
+            }
+            Err(ReadlineError::Eof) => {
+                self.done = self.current_src.is_empty();
+                println!();
+                ("".into(), true)
+            }
+            Err(error) => {
+                eprintln!("Readline error: {}", error);
+                self.done = true;
+                ("".into(), true)
+            }
         }
-
-        Ok((line, is_eof))
     }
 
     fn should_continue_repl(errors: &[ParseError]) -> bool {
         !errors.is_empty() && errors.iter().all(ParseError::allows_continuation)
     }
 
+    /// Runs the accumulated source through the bytecode `Vm` instead of the tree-walk
+    /// `Interpreter`. The VM compiles `current_src` itself (it doesn't consume the tree `Parser`'s
+    /// output), so a parse error caught above is simply re-reported here rather than re-derived
+    /// from the VM's own `Compiler`, which would otherwise duplicate the same diagnostics.
+    fn eval_vm(&mut self, errors: &[ParseError]) {
+        if !errors.is_empty() {
+            let mut diagnostics: Vec<Diagnostic> = errors.iter().map(Diagnostic::from).collect();
+            diagnostics::render_all(&mut io::stderr(), &self.current_src, &mut diagnostics);
+            return;
+        }
+        if let Err(error) = self.vm.interpret(&self.current_src) {
+            error.render(&mut io::stderr(), &self.current_src);
+        }
+    }
+
+    fn switch_backend(&mut self, backend: Backend) {
+        self.backend = backend;
+        println!("Switched to the `{}` backend.", backend);
+    }
+
     fn handle_command(&mut self, raw_cmd: &str) {
         let cmd: Vec<_> = raw_cmd
             .split_ascii_whitespace()
@@ -113,12 +206,35 @@ impl Repl {
                 Err(error) => eprintln!("{}", error),
             },
 
-            "help" => eprintln!(":exit | :lex | :ast | :help"),
+            "backend" => match cmd.get(1) {
+                Some(&"tree") => self.switch_backend(Backend::Tree),
+                Some(&"vm") => self.switch_backend(Backend::Vm),
+                _ => eprintln!(
+                    "Usage: `:backend tree|vm` (currently `{}`)",
+                    self.backend
+                ),
+            },
+            "time" => handle_bool_opt!(self.show_time),
+            "paren-free" => handle_bool_opt!(self.paren_free_conditions),
+
+            "help" => eprintln!(
+                ":exit | :lex | :ast | :backend tree|vm | :time | :paren-free | :help"
+            ),
             _ => eprintln!("Invalid command. Type `:help` for guidance."),
         }
     }
 }
 
+/// The history file's path, or `None` if the home directory can't be determined (in which case
+/// the REPL simply runs without persisted history).
+fn history_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(HISTORY_FILE_NAME))
+}
+
+fn to_io_error(error: rustyline::error::ReadlineError) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, error)
+}
+
 macro_rules! handle_bool_opt {
     ($self:ident . $option:ident) => {{
         $self.$option = !$self.$option;
@@ -0,0 +1,346 @@
+use std::{collections::HashMap, fmt, io::Write, rc::Rc};
+
+use crate::{
+    diagnostics::{self, Diagnostic},
+    span::Span,
+    vm::{
+        chunk::Chunk,
+        compiler::{CompileError, Compiler},
+        op::OpCode,
+        value::{LoxFunction, Value},
+    },
+};
+
+pub mod chunk;
+pub mod compiler;
+pub mod op;
+pub mod value;
+
+/// A stack-based bytecode VM, offered as a faster alternative to the tree-walking interpreter for
+/// hot code (e.g. tight numeric loops), at the cost of not yet supporting the full language
+/// (functions are supported but don't close over their enclosing scope, and there are no classes
+/// yet).
+pub struct Vm {
+    stack: Vec<Value>,
+    globals: HashMap<Rc<str>, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VmError {
+    Compile(Vec<CompileError>),
+    Runtime { message: String, span: Span },
+}
+
+impl VmError {
+    fn runtime(message: impl Into<String>) -> Self {
+        VmError::Runtime {
+            message: message.into(),
+            span: Span::default(),
+        }
+    }
+
+    /// Attaches the span of the instruction that was executing when a runtime error was raised;
+    /// a no-op for compile errors (which already carry their own per-error spans) and for runtime
+    /// errors that already have one attached, so a span picked up deep in a call chain survives
+    /// as the error unwinds back out through its callers' frames.
+    fn at(mut self, span: Span) -> Self {
+        if let VmError::Runtime { span: s, .. } = &mut self {
+            if *s == Span::default() {
+                *s = span;
+            }
+        }
+        self
+    }
+
+    /// Renders this error as an annotated source snippet, the same way parser/resolver/runtime
+    /// errors are rendered for the tree-walking backend.
+    pub fn render(&self, writer: &mut dyn Write, source: &str) {
+        match self {
+            VmError::Compile(errors) => {
+                let mut diagnostics: Vec<Diagnostic> = errors.iter().map(Diagnostic::from).collect();
+                diagnostics::render_all(writer, source, &mut diagnostics);
+            }
+            VmError::Runtime { message, span } => {
+                diagnostics::render(writer, source, &Diagnostic::error(*span, message.clone()));
+            }
+        }
+    }
+}
+
+impl fmt::Display for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VmError::Compile(errors) => {
+                for error in errors {
+                    writeln!(f, "{}; at position {}", error.message, error.span)?;
+                }
+                Ok(())
+            }
+            VmError::Runtime { message, span } => write!(f, "{}; at position {}", message, span),
+        }
+    }
+}
+
+impl Vm {
+    pub fn new() -> Self {
+        Self {
+            stack: Vec::with_capacity(256),
+            globals: HashMap::new(),
+        }
+    }
+
+    /// Compiles and runs the given source, starting from an empty stack but reusing this `Vm`'s
+    /// global table across calls (so a REPL session can build up state across inputs).
+    pub fn interpret(&mut self, source: &str) -> Result<(), VmError> {
+        let chunk = Compiler::compile(source).map_err(VmError::Compile)?;
+        self.stack.clear();
+        self.run(&chunk)
+    }
+
+    fn run(&mut self, chunk: &Chunk) -> Result<(), VmError> {
+        let mut ip = 0;
+        loop {
+            let instr_start = ip;
+
+            #[cfg(feature = "disassemble")]
+            {
+                print!("          ");
+                for value in &self.stack {
+                    print!("[ {} ]", value);
+                }
+                println!();
+            }
+
+            match self.execute(chunk, &mut ip, 0) {
+                Ok(Some(_)) => return Ok(()),
+                Ok(None) => {}
+                Err(error) => return Err(error.at(chunk.span_at(instr_start))),
+            }
+        }
+    }
+
+    /// Runs `function`'s body to completion in a fresh window of `self.stack` starting right
+    /// after its already-pushed arguments, then collapses that window back down to the single
+    /// returned value, leaving the stack exactly as a `Value`-returning expression would.
+    fn call_function(&mut self, function: Rc<LoxFunction>, arg_count: u8) -> Result<(), VmError> {
+        if function.arity != arg_count {
+            return Err(VmError::runtime(format!(
+                "Expected {} argument(s) but got {}",
+                function.arity, arg_count
+            )));
+        }
+
+        let base = self.stack.len() - arg_count as usize;
+        let mut ip = 0;
+        loop {
+            let instr_start = ip;
+            match self.execute(&function.chunk, &mut ip, base) {
+                Ok(Some(value)) => {
+                    self.stack.truncate(base - 1);
+                    self.push(value);
+                    return Ok(());
+                }
+                Ok(None) => {}
+                Err(error) => return Err(error.at(function.chunk.span_at(instr_start))),
+            }
+        }
+    }
+
+    /// Executes a single instruction starting at `*ip`, advancing it past the instruction's
+    /// operands. `base` is the stack index of the current frame's slot 0, used to resolve
+    /// `GetLocal`/`SetLocal` operands. Returns the function's return value once `OpCode::Return`
+    /// is reached, telling the caller (`run` or `call_function`) to stop.
+    fn execute(&mut self, chunk: &Chunk, ip: &mut usize, base: usize) -> Result<Option<Value>, VmError> {
+        let op = OpCode::try_from(chunk.read_u8(*ip)).expect("invalid opcode byte");
+        *ip += 1;
+
+        match op {
+            OpCode::Constant => {
+                let idx = chunk.read_u8(*ip) as usize;
+                *ip += 1;
+                self.push(chunk.constants[idx].clone());
+            }
+            OpCode::Nil => self.push(Value::Nil),
+            OpCode::True => self.push(Value::Boolean(true)),
+            OpCode::False => self.push(Value::Boolean(false)),
+            OpCode::Pop => {
+                self.pop();
+            }
+
+            OpCode::GetLocal => {
+                let slot = chunk.read_u8(*ip) as usize;
+                *ip += 1;
+                self.push(self.stack[base + slot].clone());
+            }
+            OpCode::SetLocal => {
+                let slot = chunk.read_u8(*ip) as usize;
+                *ip += 1;
+                self.stack[base + slot] = self.peek(0).clone();
+            }
+            OpCode::GetGlobal => {
+                let name = self.read_global_name(chunk, ip);
+                let value = self
+                    .globals
+                    .get(&name)
+                    .ok_or_else(|| VmError::runtime(format!("Undefined variable `{}`", name)))?
+                    .clone();
+                self.push(value);
+            }
+            OpCode::DefineGlobal => {
+                let name = self.read_global_name(chunk, ip);
+                let value = self.pop();
+                self.globals.insert(name, value);
+            }
+            OpCode::SetGlobal => {
+                let name = self.read_global_name(chunk, ip);
+                if !self.globals.contains_key(&name) {
+                    return Err(VmError::runtime(format!("Undefined variable `{}`", name)));
+                }
+                self.globals.insert(name, self.peek(0).clone());
+            }
+
+            OpCode::Equal => {
+                let (b, a) = (self.pop(), self.pop());
+                self.push(Value::Boolean(a.is_equal(&b)));
+            }
+            OpCode::Greater => self.number_comparison(|a, b| a > b)?,
+            OpCode::Less => self.number_comparison(|a, b| a < b)?,
+            OpCode::Add => self.add()?,
+            OpCode::Subtract => self.number_binary(|a, b| a - b)?,
+            OpCode::Multiply => self.number_binary(|a, b| a * b)?,
+            OpCode::Divide => self.divide()?,
+            OpCode::Not => {
+                let value = self.pop();
+                self.push(Value::Boolean(!value.is_truthy()));
+            }
+            OpCode::Negate => match self.pop() {
+                Value::Number(number) => self.push(Value::Number(-number)),
+                other => {
+                    return Err(VmError::runtime(format!(
+                        "Bad type for unary `-` operator: `{}`",
+                        other.type_name()
+                    )))
+                }
+            },
+
+            OpCode::Print => println!("{}", self.pop()),
+
+            OpCode::Jump => {
+                let offset = chunk.read_u16(*ip);
+                *ip += 2 + offset as usize;
+            }
+            OpCode::JumpIfFalse => {
+                let offset = chunk.read_u16(*ip);
+                *ip += 2;
+                if !self.peek(0).is_truthy() {
+                    *ip += offset as usize;
+                }
+            }
+            OpCode::Loop => {
+                let offset = chunk.read_u16(*ip);
+                *ip = *ip + 2 - offset as usize;
+            }
+
+            OpCode::Call => {
+                let arg_count = chunk.read_u8(*ip);
+                *ip += 1;
+                match self.peek(arg_count as usize).clone() {
+                    Value::Function(function) => self.call_function(function, arg_count)?,
+                    other => {
+                        return Err(VmError::runtime(format!(
+                            "Can only call functions. Got type `{}`",
+                            other.type_name()
+                        )))
+                    }
+                }
+            }
+            OpCode::Return => return Ok(Some(self.pop())),
+        }
+        Ok(None)
+    }
+
+    fn read_global_name(&self, chunk: &Chunk, ip: &mut usize) -> Rc<str> {
+        let idx = chunk.read_u8(*ip) as usize;
+        *ip += 1;
+        match &chunk.constants[idx] {
+            Value::String(name) => name.clone(),
+            _ => unreachable!("global name constant must be a string"),
+        }
+    }
+
+    fn add(&mut self) -> Result<(), VmError> {
+        let (b, a) = (self.pop(), self.pop());
+        match (a, b) {
+            (Value::Number(a), Value::Number(b)) => self.push(Value::Number(a + b)),
+            (Value::String(a), Value::String(b)) => {
+                self.push(Value::String(Rc::from(format!("{}{}", a, b))))
+            }
+            (a, b) => {
+                return Err(VmError::runtime(format!(
+                    "Binary `+` operator can only operate over two numbers or two strings. \
+                    Got types `{}` and `{}`",
+                    a.type_name(),
+                    b.type_name()
+                )))
+            }
+        }
+        Ok(())
+    }
+
+    fn divide(&mut self) -> Result<(), VmError> {
+        if let Value::Number(divisor) = self.peek(0) {
+            if *divisor == 0.0 {
+                return Err(VmError::runtime("Can not divide by zero"));
+            }
+        }
+        self.number_binary(|a, b| a / b)
+    }
+
+    fn number_binary(&mut self, op: impl Fn(f64, f64) -> f64) -> Result<(), VmError> {
+        let (b, a) = (self.pop(), self.pop());
+        match (a, b) {
+            (Value::Number(a), Value::Number(b)) => {
+                self.push(Value::Number(op(a, b)));
+                Ok(())
+            }
+            (a, b) => Err(VmError::runtime(format!(
+                "Operands must be numbers. Got types `{}` and `{}`",
+                a.type_name(),
+                b.type_name()
+            ))),
+        }
+    }
+
+    fn number_comparison(&mut self, op: impl Fn(f64, f64) -> bool) -> Result<(), VmError> {
+        let (b, a) = (self.pop(), self.pop());
+        match (a, b) {
+            (Value::Number(a), Value::Number(b)) => {
+                self.push(Value::Boolean(op(a, b)));
+                Ok(())
+            }
+            (a, b) => Err(VmError::runtime(format!(
+                "Operands must be numbers. Got types `{}` and `{}`",
+                a.type_name(),
+                b.type_name()
+            ))),
+        }
+    }
+
+    fn push(&mut self, value: Value) {
+        self.stack.push(value);
+    }
+
+    fn pop(&mut self) -> Value {
+        self.stack.pop().expect("stack underflow")
+    }
+
+    fn peek(&self, distance_from_top: usize) -> &Value {
+        &self.stack[self.stack.len() - 1 - distance_from_top]
+    }
+}
+
+impl Default for Vm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
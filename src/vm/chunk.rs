@@ -0,0 +1,156 @@
+use std::fmt::Write;
+
+use crate::{
+    span::Span,
+    vm::{op::OpCode, value::Value},
+};
+
+/// A sequence of bytecode instructions plus the constant pool they reference.
+#[derive(Debug, Default)]
+pub struct Chunk {
+    pub code: Vec<u8>,
+    pub constants: Vec<Value>,
+
+    /// Run-length-encoded source spans: each entry covers `run_length` consecutive bytes of
+    /// `code` that originated from the same `Span`, so a run of bytes sharing a span doesn't need
+    /// one full `Span` each.
+    spans: Vec<(usize, Span)>,
+}
+
+impl Chunk {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an opcode attributed to `span`, returning the index it was written at.
+    pub fn write_op(&mut self, op: OpCode, span: Span) -> usize {
+        self.write_u8(op.as_u8(), span)
+    }
+
+    /// Appends a raw byte attributed to `span`, returning the index it was written at.
+    pub fn write_u8(&mut self, byte: u8, span: Span) -> usize {
+        self.record_span(span);
+        self.code.push(byte);
+        self.code.len() - 1
+    }
+
+    /// Appends a big-endian `u16` attributed to `span`, returning the index of its first byte.
+    pub fn write_u16(&mut self, value: u16, span: Span) -> usize {
+        let [hi, lo] = value.to_be_bytes();
+        let at = self.write_u8(hi, span);
+        self.write_u8(lo, span);
+        at
+    }
+
+    /// Coalesces `span` into the running span table: extends the last run if it's for the same
+    /// span, otherwise starts a new one.
+    fn record_span(&mut self, span: Span) {
+        match self.spans.last_mut() {
+            Some((run_length, last_span)) if *last_span == span => *run_length += 1,
+            _ => self.spans.push((1, span)),
+        }
+    }
+
+    /// Resolves the `Span` that produced the instruction byte at `code_index`, by walking the
+    /// run-length-encoded span table.
+    pub fn span_at(&self, code_index: usize) -> Span {
+        let mut remaining = code_index;
+        for &(run_length, span) in &self.spans {
+            if remaining < run_length {
+                return span;
+            }
+            remaining -= run_length;
+        }
+        panic!("code index {} out of bounds for this chunk's span table", code_index)
+    }
+
+    /// Renders every instruction in this chunk under the given `name` into a string, one line per
+    /// instruction: byte offset, source line (with repeated lines shown as `|`), opcode mnemonic
+    /// and its operand, with constant-pool and jump operands resolved to what they actually mean.
+    /// Used by the `--disassemble` CLI mode and by `Compiler::compile` itself when its own
+    /// `disassemble`-feature trace is compiled in.
+    pub fn disassemble(&self, name: &str, line_map: &crate::span::LineMap) -> String {
+        let mut out = format!("== {} ==\n", name);
+        let mut offset = 0;
+        let mut last_line = None;
+        while offset < self.code.len() {
+            offset = self.disassemble_instruction(&mut out, offset, line_map, &mut last_line);
+        }
+        out
+    }
+
+    /// Disassembles the single instruction at `offset`, appending its rendering to `out` and
+    /// returning the offset of the next instruction.
+    fn disassemble_instruction(
+        &self,
+        out: &mut String,
+        offset: usize,
+        line_map: &crate::span::LineMap,
+        last_line: &mut Option<usize>,
+    ) -> usize {
+        let op = OpCode::try_from(self.code[offset]).expect("invalid opcode byte");
+        let line = line_map.locate(self.span_at(offset).lo).line;
+        if *last_line == Some(line) {
+            write!(out, "{:04}    | ", offset).unwrap();
+        } else {
+            write!(out, "{:04} {:4} ", offset, line).unwrap();
+            *last_line = Some(line);
+        }
+
+        match op {
+            OpCode::Constant | OpCode::GetGlobal | OpCode::DefineGlobal | OpCode::SetGlobal => {
+                let idx = self.code[offset + 1];
+                writeln!(out, "{:?} {:4} {:?}", op, idx, self.constants[idx as usize]).unwrap();
+                offset + 2
+            }
+            OpCode::GetLocal | OpCode::SetLocal => {
+                let slot = self.code[offset + 1];
+                writeln!(out, "{:?} {:4}", op, slot).unwrap();
+                offset + 2
+            }
+            OpCode::Jump | OpCode::JumpIfFalse => {
+                let delta = self.read_u16(offset + 1);
+                let target = offset + 3 + delta as usize;
+                writeln!(out, "{:?} {:4} -> {}", op, delta, target).unwrap();
+                offset + 3
+            }
+            OpCode::Loop => {
+                let delta = self.read_u16(offset + 1);
+                let target = offset + 3 - delta as usize;
+                writeln!(out, "{:?} {:4} -> {}", op, delta, target).unwrap();
+                offset + 3
+            }
+            OpCode::Call => {
+                let arg_count = self.code[offset + 1];
+                writeln!(out, "{:?} ({} args)", op, arg_count).unwrap();
+                offset + 2
+            }
+            _ => {
+                writeln!(out, "{:?}", op).unwrap();
+                offset + 1
+            }
+        }
+    }
+
+    /// Overwrites the big-endian `u16` placeholder at `at` (as returned by `write_u16`).
+    pub fn patch_u16(&mut self, at: usize, value: u16) {
+        let [hi, lo] = value.to_be_bytes();
+        self.code[at] = hi;
+        self.code[at + 1] = lo;
+    }
+
+    /// Adds a constant to the pool, returning its index. Panics if the pool overflows a `u8`,
+    /// since `Constant`-family opcodes only carry a single-byte operand.
+    pub fn add_constant(&mut self, value: Value) -> u8 {
+        self.constants.push(value);
+        u8::try_from(self.constants.len() - 1).expect("too many constants in one chunk")
+    }
+
+    pub fn read_u8(&self, at: usize) -> u8 {
+        self.code[at]
+    }
+
+    pub fn read_u16(&self, at: usize) -> u16 {
+        u16::from_be_bytes([self.code[at], self.code[at + 1]])
+    }
+}
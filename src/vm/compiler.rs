@@ -0,0 +1,783 @@
+use std::{collections::HashMap, mem, rc::Rc};
+
+use crate::{
+    diagnostics::Diagnostic,
+    parser::scanner::Scanner,
+    span::Span,
+    symbol::Symbol,
+    token::{Token, TokenKind},
+    vm::{
+        chunk::Chunk,
+        op::OpCode,
+        value::{LoxFunction, Value},
+    },
+};
+
+/// Compiles Lox source straight into a `Chunk`, without building an intermediate AST.
+///
+/// Unlike the tree-walking `Parser`/`Resolver` pair, local variables are resolved to stack slots
+/// right here at compile time; globals stay late-bound by name, resolved at runtime by the `Vm`.
+pub struct Compiler<'s> {
+    scanner: Scanner<'s>,
+    current: Token,
+    previous: Token,
+    chunk: Chunk,
+    locals: Vec<Local>,
+    scope_depth: i32,
+    /// Whether `chunk` is currently a function body rather than top-level script code; gates
+    /// `return` statements, which only make sense inside a function.
+    in_function: bool,
+    /// Caches the constant-pool index a global's name was already emitted under in `chunk`, so
+    /// referring to the same global repeatedly
+    /// reuses one `Value::String` instead of growing the pool by a duplicate every time. Keyed by
+    /// `Symbol` rather than the resolved `&str` since identifiers are already interned there.
+    /// Scoped to `chunk`, so it's swapped out alongside it in `function`.
+    string_constants: HashMap<Symbol, u8>,
+    errors: Vec<CompileError>,
+}
+
+struct Local {
+    name: Symbol,
+    depth: i32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompileError {
+    pub message: String,
+    pub span: Span,
+}
+
+type CResult<T> = Result<T, CompileError>;
+
+impl From<&CompileError> for Diagnostic {
+    fn from(error: &CompileError) -> Self {
+        Diagnostic::error(error.span, error.message.clone())
+    }
+}
+
+impl<'s> Compiler<'s> {
+    pub fn compile(source: &'s str) -> Result<Chunk, Vec<CompileError>> {
+        let mut compiler = Compiler {
+            scanner: Scanner::new(source),
+            current: Token::dummy(),
+            previous: Token::dummy(),
+            chunk: Chunk::new(),
+            locals: Vec::new(),
+            scope_depth: 0,
+            in_function: false,
+            string_constants: HashMap::new(),
+            errors: Vec::new(),
+        };
+        compiler.advance();
+        while !compiler.is_at_end() {
+            if let Err(error) = compiler.declaration() {
+                compiler.errors.push(error);
+                compiler.synchronize();
+            }
+        }
+        // Every chunk (script or function body) ends by leaving a return value on the stack for
+        // its caller, so `Vm::run`/`call_function` can pop it with the same `OpCode::Return`
+        // handling either way; the script's own return value is simply discarded.
+        compiler.chunk.write_op(OpCode::Nil, compiler.previous.span);
+        compiler.chunk.write_op(OpCode::Return, compiler.previous.span);
+
+        if compiler.errors.is_empty() {
+            #[cfg(feature = "disassemble")]
+            print!(
+                "{}",
+                compiler.chunk.disassemble("code", &crate::span::LineMap::new(source))
+            );
+            Ok(compiler.chunk)
+        } else {
+            Err(compiler.errors)
+        }
+    }
+
+    //
+    // Statements
+    //
+
+    fn declaration(&mut self) -> CResult<()> {
+        if self.take(TokenKind::Fun) {
+            self.fun_declaration()
+        } else if self.take(TokenKind::Var) {
+            self.var_declaration()
+        } else {
+            self.statement()
+        }
+    }
+
+    fn fun_declaration(&mut self) -> CResult<()> {
+        let name = self.consume_ident("Expected function name")?;
+        if self.scope_depth > 0 {
+            // Reserved here for the function value `emit_constant` leaves on the stack below. The
+            // body itself can't see this slot (see `function`), so a local function that calls
+            // itself by name resolves that call as a global.
+            self.locals.push(Local { name, depth: self.scope_depth });
+        }
+
+        let function = self.function(Some(name))?;
+        self.emit_constant(Value::Function(Rc::new(function)), self.previous.span);
+
+        if self.scope_depth == 0 {
+            let idx = self.string_constant(name);
+            self.chunk.write_op(OpCode::DefineGlobal, self.previous.span);
+            self.chunk.write_u8(idx, self.previous.span);
+        }
+        Ok(())
+    }
+
+    /// Compiles a function's parameter list and body into their own `Chunk`, by swapping it in
+    /// for `self.chunk`/`self.locals`/`self.scope_depth` while parsing (the token stream itself
+    /// stays shared, since this is still one single-pass walk over it). Note that the swapped-out
+    /// locals are *not* visible while compiling the body, so (absent closures/upvalues) a nested
+    /// function can't refer to its enclosing function's locals, including its own slot if it was
+    /// declared as a local — such a reference resolves as a global instead and fails at runtime.
+    fn function(&mut self, name: Option<Symbol>) -> CResult<LoxFunction> {
+        let outer_chunk = mem::take(&mut self.chunk);
+        let outer_locals = mem::take(&mut self.locals);
+        let outer_scope_depth = mem::replace(&mut self.scope_depth, 1);
+        let outer_in_function = mem::replace(&mut self.in_function, true);
+        let outer_string_constants = mem::take(&mut self.string_constants);
+
+        let mut arity: u8 = 0;
+        self.consume(TokenKind::LeftParen, "Expected `(` after function name")?;
+        if !self.is(TokenKind::RightParen) {
+            loop {
+                arity = arity
+                    .checked_add(1)
+                    .expect("too many parameters in one function declaration");
+                let param = self.consume_ident("Expected parameter name")?;
+                self.locals.push(Local { name: param, depth: self.scope_depth });
+                if !self.take(TokenKind::Comma) {
+                    break;
+                }
+            }
+        }
+        self.consume(TokenKind::RightParen, "Expected `)` after parameters")?;
+        self.consume(TokenKind::LeftBrace, "Expected `{` before function body")?;
+        self.block()?;
+
+        self.chunk.write_op(OpCode::Nil, self.previous.span);
+        self.chunk.write_op(OpCode::Return, self.previous.span);
+
+        let chunk = mem::replace(&mut self.chunk, outer_chunk);
+        self.locals = outer_locals;
+        self.scope_depth = outer_scope_depth;
+        self.in_function = outer_in_function;
+        self.string_constants = outer_string_constants;
+
+        Ok(LoxFunction {
+            name: name.map(|name| Rc::from(name.resolve())),
+            arity,
+            chunk,
+        })
+    }
+
+    fn var_declaration(&mut self) -> CResult<()> {
+        let name = self.consume_ident("Expected variable name")?;
+
+        if self.take(TokenKind::Equal) {
+            self.expression()?;
+        } else {
+            self.chunk.write_op(OpCode::Nil, self.previous.span);
+        }
+        self.consume(TokenKind::Semicolon, "Expected `;` after variable declaration")?;
+
+        self.define_variable(name);
+        Ok(())
+    }
+
+    fn statement(&mut self) -> CResult<()> {
+        use TokenKind::*;
+        if self.take(Print) {
+            self.print_statement()
+        } else if self.take(If) {
+            self.if_statement()
+        } else if self.take(While) {
+            self.while_statement()
+        } else if self.take(For) {
+            self.for_statement()
+        } else if self.take(Return) {
+            self.return_statement()
+        } else if self.take(LeftBrace) {
+            self.begin_scope();
+            self.block()?;
+            self.end_scope();
+            Ok(())
+        } else {
+            self.expr_statement()
+        }
+    }
+
+    fn print_statement(&mut self) -> CResult<()> {
+        self.expression()?;
+        self.consume(TokenKind::Semicolon, "Expected `;` after value")?;
+        self.chunk.write_op(OpCode::Print, self.previous.span);
+        Ok(())
+    }
+
+    fn if_statement(&mut self) -> CResult<()> {
+        self.consume(TokenKind::LeftParen, "Expected `(` after `if`")?;
+        self.expression()?;
+        self.consume(TokenKind::RightParen, "Expected `)` after condition")?;
+
+        let then_jump = self.emit_jump(OpCode::JumpIfFalse);
+        self.chunk.write_op(OpCode::Pop, self.previous.span);
+        self.statement()?;
+
+        let else_jump = self.emit_jump(OpCode::Jump);
+        self.patch_jump(then_jump);
+        self.chunk.write_op(OpCode::Pop, self.previous.span);
+
+        if self.take(TokenKind::Else) {
+            self.statement()?;
+        }
+        self.patch_jump(else_jump);
+        Ok(())
+    }
+
+    fn while_statement(&mut self) -> CResult<()> {
+        let loop_start = self.chunk.code.len();
+        self.consume(TokenKind::LeftParen, "Expected `(` after `while`")?;
+        self.expression()?;
+        self.consume(TokenKind::RightParen, "Expected `)` after condition")?;
+
+        let exit_jump = self.emit_jump(OpCode::JumpIfFalse);
+        self.chunk.write_op(OpCode::Pop, self.previous.span);
+        self.statement()?;
+        self.emit_loop(loop_start);
+
+        self.patch_jump(exit_jump);
+        self.chunk.write_op(OpCode::Pop, self.previous.span);
+        Ok(())
+    }
+
+    /// Desugars `for (init; cond; incr) body` into the same `Jump`/`Loop` shape `while_statement`
+    /// already emits, the same way the tree-walk `Parser` desugars it into a `while` `Stmt` — just
+    /// done here directly against bytecode instead of against an intermediate AST, since this
+    /// compiler has no AST to desugar through. `init`'s scope is this statement's own scope, so a
+    /// `var` declared there is popped by `end_scope` once the loop is done.
+    fn for_statement(&mut self) -> CResult<()> {
+        self.begin_scope();
+        self.consume(TokenKind::LeftParen, "Expected `(` after `for`")?;
+
+        if self.take(TokenKind::Semicolon) {
+            // No initializer.
+        } else if self.take(TokenKind::Var) {
+            self.var_declaration()?;
+        } else {
+            self.expr_statement()?;
+        }
+
+        let mut loop_start = self.chunk.code.len();
+
+        let mut exit_jump = None;
+        if self.take(TokenKind::Semicolon) {
+            // No condition: the loop only exits via a `break` (once supported) or never.
+        } else {
+            self.expression()?;
+            self.consume(TokenKind::Semicolon, "Expected `;` after loop condition")?;
+            exit_jump = Some(self.emit_jump(OpCode::JumpIfFalse));
+            self.chunk.write_op(OpCode::Pop, self.previous.span);
+        }
+
+        if !self.is(TokenKind::RightParen) {
+            // The increment is compiled once, right after the condition, but must run after the
+            // body, not before it: jump over it into the body first, then loop back into it.
+            let body_jump = self.emit_jump(OpCode::Jump);
+            let increment_start = self.chunk.code.len();
+            self.expression()?;
+            self.chunk.write_op(OpCode::Pop, self.previous.span);
+            self.consume(TokenKind::RightParen, "Expected `)` after `for` clauses")?;
+
+            self.emit_loop(loop_start);
+            loop_start = increment_start;
+            self.patch_jump(body_jump);
+        } else {
+            self.consume(TokenKind::RightParen, "Expected `)` after `for` clauses")?;
+        }
+
+        self.statement()?;
+        self.emit_loop(loop_start);
+
+        if let Some(exit_jump) = exit_jump {
+            self.patch_jump(exit_jump);
+            self.chunk.write_op(OpCode::Pop, self.previous.span);
+        }
+
+        self.end_scope();
+        Ok(())
+    }
+
+    fn return_statement(&mut self) -> CResult<()> {
+        let span = self.previous.span;
+        if !self.in_function {
+            return Err(CompileError {
+                message: "Can't return from top-level code".into(),
+                span,
+            });
+        }
+
+        if self.take(TokenKind::Semicolon) {
+            self.chunk.write_op(OpCode::Nil, span);
+        } else {
+            self.expression()?;
+            self.consume(TokenKind::Semicolon, "Expected `;` after return value")?;
+        }
+        self.chunk.write_op(OpCode::Return, span);
+        Ok(())
+    }
+
+    fn block(&mut self) -> CResult<()> {
+        while !self.is(TokenKind::RightBrace) && !self.is_at_end() {
+            if let Err(error) = self.declaration() {
+                self.errors.push(error);
+                self.synchronize();
+            }
+        }
+        self.consume(TokenKind::RightBrace, "Expected `}` after block")?;
+        Ok(())
+    }
+
+    fn expr_statement(&mut self) -> CResult<()> {
+        self.expression()?;
+        self.consume(TokenKind::Semicolon, "Expected `;` after expression")?;
+        self.chunk.write_op(OpCode::Pop, self.previous.span);
+        Ok(())
+    }
+
+    //
+    // Expressions (precedence-climbing / Pratt parsing)
+    //
+
+    fn expression(&mut self) -> CResult<()> {
+        self.parse_precedence(Precedence::Assignment)
+    }
+
+    /// The core Pratt-parsing routine: consumes one token and runs its prefix rule, then keeps
+    /// consuming and running infix rules as long as the upcoming token binds at least as tightly
+    /// as `min_prec`. Binary infix rules recurse via `rule.precedence.next()`, which is what
+    /// makes them left-associative.
+    fn parse_precedence(&mut self, min_prec: Precedence) -> CResult<()> {
+        self.advance();
+        let can_assign = min_prec <= Precedence::Assignment;
+
+        let prefix = ParseRule::of(&self.previous.kind).prefix.ok_or_else(|| CompileError {
+            message: "Expected an expression".into(),
+            span: self.previous.span,
+        })?;
+        prefix(self, can_assign)?;
+
+        while min_prec <= ParseRule::of(&self.current.kind).precedence {
+            self.advance();
+            let infix = ParseRule::of(&self.previous.kind)
+                .infix
+                .expect("token matched the precedence check, so it must have an infix rule");
+            infix(self, can_assign)?;
+        }
+
+        if can_assign && self.take(TokenKind::Equal) {
+            return Err(CompileError {
+                message: "Invalid assignment target".into(),
+                span: self.previous.span,
+            });
+        }
+        Ok(())
+    }
+
+    fn number(&mut self, _can_assign: bool) -> CResult<()> {
+        match self.previous.kind {
+            TokenKind::Number(n) => self.emit_constant(Value::Number(n), self.previous.span),
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+
+    fn string(&mut self, _can_assign: bool) -> CResult<()> {
+        match &self.previous.kind {
+            TokenKind::String(s) => {
+                let value = Value::String(s.resolve().into());
+                self.emit_constant(value, self.previous.span);
+            }
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+
+    fn literal(&mut self, _can_assign: bool) -> CResult<()> {
+        let op = match self.previous.kind {
+            TokenKind::True => OpCode::True,
+            TokenKind::False => OpCode::False,
+            TokenKind::Nil => OpCode::Nil,
+            _ => unreachable!(),
+        };
+        self.chunk.write_op(op, self.previous.span);
+        Ok(())
+    }
+
+    fn grouping(&mut self, _can_assign: bool) -> CResult<()> {
+        self.expression()?;
+        self.consume(TokenKind::RightParen, "Expected `)` after expression")?;
+        Ok(())
+    }
+
+    /// Infix `(`: the callee's value is already sitting on the stack from the just-parsed prefix
+    /// expression, so this only needs to parse and push the arguments, then emit the call itself.
+    fn call(&mut self, _can_assign: bool) -> CResult<()> {
+        let span = self.previous.span;
+        let arg_count = self.argument_list()?;
+        self.chunk.write_op(OpCode::Call, span);
+        self.chunk.write_u8(arg_count, span);
+        Ok(())
+    }
+
+    fn argument_list(&mut self) -> CResult<u8> {
+        let mut count: u8 = 0;
+        if !self.is(TokenKind::RightParen) {
+            loop {
+                self.expression()?;
+                count = count
+                    .checked_add(1)
+                    .expect("too many arguments in one call");
+                if !self.take(TokenKind::Comma) {
+                    break;
+                }
+            }
+        }
+        self.consume(TokenKind::RightParen, "Expected `)` after arguments")?;
+        Ok(count)
+    }
+
+    fn unary(&mut self, _can_assign: bool) -> CResult<()> {
+        let operator = self.previous.kind.clone();
+        let operator_span = self.previous.span;
+        self.parse_precedence(Precedence::Unary)?;
+        let op = match operator {
+            TokenKind::Minus => OpCode::Negate,
+            TokenKind::Bang => OpCode::Not,
+            _ => unreachable!(),
+        };
+        self.chunk.write_op(op, operator_span);
+        Ok(())
+    }
+
+    fn binary(&mut self, _can_assign: bool) -> CResult<()> {
+        use TokenKind::*;
+        let operator = self.previous.kind.clone();
+        let operator_span = self.previous.span;
+        let prec = ParseRule::of(&operator).precedence;
+        self.parse_precedence(prec.next())?;
+
+        let op = match operator {
+            Plus => OpCode::Add,
+            Minus => OpCode::Subtract,
+            Star => OpCode::Multiply,
+            Slash => OpCode::Divide,
+            EqualEqual => OpCode::Equal,
+            Greater => OpCode::Greater,
+            Less => OpCode::Less,
+            BangEqual => {
+                self.chunk.write_op(OpCode::Equal, operator_span);
+                OpCode::Not
+            }
+            GreaterEqual => {
+                self.chunk.write_op(OpCode::Less, operator_span);
+                OpCode::Not
+            }
+            LessEqual => {
+                self.chunk.write_op(OpCode::Greater, operator_span);
+                OpCode::Not
+            }
+            _ => unreachable!("Invalid infix operator ({:?}).", operator),
+        };
+        self.chunk.write_op(op, operator_span);
+        Ok(())
+    }
+
+    fn and(&mut self, _can_assign: bool) -> CResult<()> {
+        let end_jump = self.emit_jump(OpCode::JumpIfFalse);
+        self.chunk.write_op(OpCode::Pop, self.previous.span);
+        self.parse_precedence(Precedence::And)?;
+        self.patch_jump(end_jump);
+        Ok(())
+    }
+
+    fn or(&mut self, _can_assign: bool) -> CResult<()> {
+        let else_jump = self.emit_jump(OpCode::JumpIfFalse);
+        let end_jump = self.emit_jump(OpCode::Jump);
+        self.patch_jump(else_jump);
+        self.chunk.write_op(OpCode::Pop, self.previous.span);
+        self.parse_precedence(Precedence::Or)?;
+        self.patch_jump(end_jump);
+        Ok(())
+    }
+
+    fn ident_expr(&mut self, can_assign: bool) -> CResult<()> {
+        let name = match self.previous.kind {
+            TokenKind::Identifier(name) => name,
+            _ => unreachable!(),
+        };
+        self.variable(name, can_assign)
+    }
+
+    fn variable(&mut self, name: Symbol, can_assign: bool) -> CResult<()> {
+        let span = self.previous.span;
+        if can_assign && self.take(TokenKind::Equal) {
+            self.expression()?;
+            if let Some(slot) = self.resolve_local(name) {
+                self.chunk.write_op(OpCode::SetLocal, span);
+                self.chunk.write_u8(slot, span);
+            } else {
+                let idx = self.string_constant(name);
+                self.chunk.write_op(OpCode::SetGlobal, span);
+                self.chunk.write_u8(idx, span);
+            }
+            return Ok(());
+        }
+
+        if let Some(slot) = self.resolve_local(name) {
+            self.chunk.write_op(OpCode::GetLocal, span);
+            self.chunk.write_u8(slot, span);
+        } else {
+            let idx = self.string_constant(name);
+            self.chunk.write_op(OpCode::GetGlobal, span);
+            self.chunk.write_u8(idx, span);
+        }
+        Ok(())
+    }
+
+    //
+    // Variable / scope bookkeeping
+    //
+
+    fn define_variable(&mut self, name: Symbol) {
+        if self.scope_depth > 0 {
+            self.locals.push(Local {
+                name,
+                depth: self.scope_depth,
+            });
+            return;
+        }
+        let idx = self.string_constant(name);
+        self.chunk.write_op(OpCode::DefineGlobal, self.previous.span);
+        self.chunk.write_u8(idx, self.previous.span);
+    }
+
+    fn resolve_local(&self, name: Symbol) -> Option<u8> {
+        self.locals
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, local)| local.name == name)
+            .map(|(slot, _)| slot as u8)
+    }
+
+    fn begin_scope(&mut self) {
+        self.scope_depth += 1;
+    }
+
+    fn end_scope(&mut self) {
+        self.scope_depth -= 1;
+        while let Some(local) = self.locals.last() {
+            if local.depth <= self.scope_depth {
+                break;
+            }
+            self.locals.pop();
+            self.chunk.write_op(OpCode::Pop, self.previous.span);
+        }
+    }
+
+    //
+    // Emission helpers
+    //
+
+    fn emit_constant(&mut self, value: Value, span: Span) {
+        let idx = self.chunk.add_constant(value);
+        self.chunk.write_op(OpCode::Constant, span);
+        self.chunk.write_u8(idx, span);
+    }
+
+    /// Returns the constant-pool index holding `name` as a `Value::String`, reusing the index
+    /// from a previous call with the same `name` instead of emitting a duplicate constant. Used
+    /// everywhere a global variable's name needs to be a runtime value (`DefineGlobal`,
+    /// `GetGlobal`, `SetGlobal`), since those are looked up by name rather than by stack slot.
+    fn string_constant(&mut self, name: Symbol) -> u8 {
+        if let Some(&idx) = self.string_constants.get(&name) {
+            return idx;
+        }
+        let idx = self.chunk.add_constant(Value::String(name.resolve().into()));
+        self.string_constants.insert(name, idx);
+        idx
+    }
+
+    /// Emits a jump instruction with a placeholder offset, returning the index to patch later.
+    fn emit_jump(&mut self, op: OpCode) -> usize {
+        let span = self.previous.span;
+        self.chunk.write_op(op, span);
+        self.chunk.write_u16(0xFFFF, span)
+    }
+
+    /// Backpatches a previously-emitted jump to land right after the instructions emitted since.
+    fn patch_jump(&mut self, at: usize) {
+        let offset = self.chunk.code.len() - at - 2;
+        self.chunk
+            .patch_u16(at, u16::try_from(offset).expect("jump body too large"));
+    }
+
+    fn emit_loop(&mut self, loop_start: usize) {
+        let span = self.previous.span;
+        self.chunk.write_op(OpCode::Loop, span);
+        let offset = self.chunk.code.len() - loop_start + 2;
+        self.chunk
+            .write_u16(u16::try_from(offset).expect("loop body too large"), span);
+    }
+
+    //
+    // Token stream helpers
+    //
+
+    fn advance(&mut self) -> &Token {
+        let next = loop {
+            let token = self.scanner.next().expect("cannot advance past Eof");
+            if let TokenKind::Error(error) = token.kind {
+                self.errors.push(CompileError {
+                    message: error.to_string(),
+                    span: token.span,
+                });
+                continue;
+            }
+            break token;
+        };
+        self.previous = mem::replace(&mut self.current, next);
+        &self.previous
+    }
+
+    fn is(&self, kind: TokenKind) -> bool {
+        mem::discriminant(&self.current.kind) == mem::discriminant(&kind)
+    }
+
+    fn take(&mut self, kind: TokenKind) -> bool {
+        let matches = self.is(kind);
+        if matches {
+            self.advance();
+        }
+        matches
+    }
+
+    fn consume(&mut self, kind: TokenKind, message: &str) -> CResult<&Token> {
+        if self.is(kind) {
+            Ok(self.advance())
+        } else {
+            Err(CompileError {
+                message: message.into(),
+                span: self.current.span,
+            })
+        }
+    }
+
+    fn consume_ident(&mut self, message: &str) -> CResult<Symbol> {
+        let token = self.consume(TokenKind::Identifier("<ident>".into()), message)?;
+        match &token.kind {
+            TokenKind::Identifier(name) => Ok(*name),
+            _ => unreachable!(),
+        }
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.current.kind == TokenKind::Eof
+    }
+
+    /// Discards tokens until a likely statement boundary, so one bad statement doesn't cascade
+    /// into spurious errors for the rest of the file.
+    fn synchronize(&mut self) {
+        use TokenKind::*;
+        while !self.is_at_end() {
+            if self.previous.kind == Semicolon {
+                return;
+            }
+            match self.current.kind {
+                Class | Fun | Var | For | If | While | Break | Continue | Print | Return => {
+                    return
+                }
+                _ => {
+                    self.advance();
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    None,
+    Assignment,
+    Or,
+    And,
+    Equality,
+    Comparison,
+    Term,
+    Factor,
+    Unary,
+    Call,
+    Primary,
+}
+
+impl Precedence {
+    fn next(self) -> Precedence {
+        match self {
+            Precedence::None => Precedence::Assignment,
+            Precedence::Assignment => Precedence::Or,
+            Precedence::Or => Precedence::And,
+            Precedence::And => Precedence::Equality,
+            Precedence::Equality => Precedence::Comparison,
+            Precedence::Comparison => Precedence::Term,
+            Precedence::Term => Precedence::Factor,
+            Precedence::Factor => Precedence::Unary,
+            Precedence::Unary => Precedence::Call,
+            Precedence::Call | Precedence::Primary => Precedence::Primary,
+        }
+    }
+}
+
+/// A Pratt-parsing function bound to a token kind: either a prefix rule (called once, at the
+/// start of an expression) or an infix rule (called with the already-parsed left operand sitting
+/// on the stack).
+type ParseFn = fn(&mut Compiler<'_>, bool) -> CResult<()>;
+
+/// What to do when a given `TokenKind` is seen in prefix or infix position, and how tightly an
+/// infix use of it binds.
+struct ParseRule {
+    prefix: Option<ParseFn>,
+    infix: Option<ParseFn>,
+    precedence: Precedence,
+}
+
+impl ParseRule {
+    /// Looks up the parse rule for a token kind. This is the static table the Pratt parser drives
+    /// off of: `parse_precedence` reads `prefix`/`infix` to know which function to call, and
+    /// `precedence` to know when to stop climbing.
+    fn of(kind: &TokenKind) -> ParseRule {
+        use TokenKind::*;
+        let (prefix, infix, precedence): (Option<ParseFn>, Option<ParseFn>, Precedence) =
+            match kind {
+                Number(_) => (Some(Compiler::number), None, Precedence::None),
+                String(_) => (Some(Compiler::string), None, Precedence::None),
+                True | False | Nil => (Some(Compiler::literal), None, Precedence::None),
+                Identifier(_) => (Some(Compiler::ident_expr), None, Precedence::None),
+                LeftParen => (Some(Compiler::grouping), Some(Compiler::call), Precedence::Call),
+                Bang => (Some(Compiler::unary), None, Precedence::None),
+                Minus => (Some(Compiler::unary), Some(Compiler::binary), Precedence::Term),
+                Plus => (None, Some(Compiler::binary), Precedence::Term),
+                Star | Slash => (None, Some(Compiler::binary), Precedence::Factor),
+                EqualEqual | BangEqual => (None, Some(Compiler::binary), Precedence::Equality),
+                Less | LessEqual | Greater | GreaterEqual => {
+                    (None, Some(Compiler::binary), Precedence::Comparison)
+                }
+                And => (None, Some(Compiler::and), Precedence::And),
+                Or => (None, Some(Compiler::or), Precedence::Or),
+                _ => (None, None, Precedence::None),
+            };
+        ParseRule { prefix, infix, precedence }
+    }
+}
@@ -0,0 +1,90 @@
+/// A single bytecode operation understood by the `Vm`.
+///
+/// Each variant is encoded as its discriminant byte, optionally followed by operand bytes that
+/// are documented per-variant below. See `Chunk` for how operands are laid out in the byte stream.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum OpCode {
+    /// Operand: 1-byte index into the chunk's constant pool.
+    Constant,
+    Nil,
+    True,
+    False,
+    Pop,
+
+    /// Operand: 1-byte stack slot, relative to the current frame's base.
+    GetLocal,
+    /// Operand: 1-byte stack slot, relative to the current frame's base.
+    SetLocal,
+    /// Operand: 1-byte index into the constant pool holding the global's name.
+    GetGlobal,
+    /// Operand: 1-byte index into the constant pool holding the global's name.
+    DefineGlobal,
+    /// Operand: 1-byte index into the constant pool holding the global's name.
+    SetGlobal,
+
+    Equal,
+    Greater,
+    Less,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Not,
+    Negate,
+
+    Print,
+
+    /// Operand: 2-byte (big-endian) forward offset, patched once the jump target is known.
+    Jump,
+    /// Operand: 2-byte (big-endian) forward offset, patched once the jump target is known.
+    JumpIfFalse,
+    /// Operand: 2-byte (big-endian) backward offset.
+    Loop,
+
+    /// Operand: 1-byte argument count.
+    Call,
+    Return,
+}
+
+impl OpCode {
+    pub const fn as_u8(self) -> u8 {
+        self as u8
+    }
+}
+
+impl TryFrom<u8> for OpCode {
+    type Error = ();
+
+    fn try_from(byte: u8) -> Result<Self, Self::Error> {
+        use OpCode::*;
+        const TABLE: &[OpCode] = &[
+            Constant,
+            Nil,
+            True,
+            False,
+            Pop,
+            GetLocal,
+            SetLocal,
+            GetGlobal,
+            DefineGlobal,
+            SetGlobal,
+            Equal,
+            Greater,
+            Less,
+            Add,
+            Subtract,
+            Multiply,
+            Divide,
+            Not,
+            Negate,
+            Print,
+            Jump,
+            JumpIfFalse,
+            Loop,
+            Call,
+            Return,
+        ];
+        TABLE.get(byte as usize).copied().ok_or(())
+    }
+}
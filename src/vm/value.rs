@@ -0,0 +1,99 @@
+use std::{
+    fmt::{self, Display},
+    rc::Rc,
+};
+
+use crate::vm::chunk::Chunk;
+
+/// A runtime value in the bytecode VM.
+///
+/// This is a leaner counterpart to `data::LoxValue`: the VM does not yet support classes, and
+/// functions don't close over their enclosing scope (no upvalues), so only what `Compiler`
+/// actually emits is represented here.
+#[derive(Clone)]
+pub enum Value {
+    Number(f64),
+    Boolean(bool),
+    String(Rc<str>),
+    Function(Rc<LoxFunction>),
+    Nil,
+}
+
+impl Value {
+    pub fn type_name(&self) -> &'static str {
+        use Value::*;
+        match self {
+            Number(_) => "number",
+            Boolean(_) => "boolean",
+            String(_) => "string",
+            Function(_) => "function",
+            Nil => "nil",
+        }
+    }
+
+    /// Truthiness follows the same rule as the tree-walking interpreter: only `false` and `nil`
+    /// are falsy.
+    pub fn is_truthy(&self) -> bool {
+        !matches!(self, Value::Boolean(false) | Value::Nil)
+    }
+
+    pub fn is_equal(&self, other: &Value) -> bool {
+        use Value::*;
+        match (self, other) {
+            (Number(a), Number(b)) => a == b,
+            (Boolean(a), Boolean(b)) => a == b,
+            (String(a), String(b)) => a == b,
+            (Function(a), Function(b)) => Rc::ptr_eq(a, b),
+            (Nil, Nil) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use Value::*;
+        match self {
+            Number(number) => {
+                if number.floor() == *number {
+                    write!(f, "{:.0}", number)
+                } else {
+                    Display::fmt(number, f)
+                }
+            }
+            Boolean(boolean) => Display::fmt(boolean, f),
+            String(string) => f.write_str(string),
+            Function(function) => Display::fmt(function.as_ref(), f),
+            Nil => f.write_str("nil"),
+        }
+    }
+}
+
+/// Debug-formats the same as `Display`, except strings are quoted, so `disassemble` can tell a
+/// constant's type apart at a glance (e.g. distinguishing the number `1` from the string `"1"`).
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::String(string) => write!(f, "\"{}\"", string),
+            other => Display::fmt(other, f),
+        }
+    }
+}
+
+/// A compiled function: its parameter count and the bytecode for its body, ready to be executed
+/// in a fresh stack window by the `Vm` whenever `OpCode::Call` targets it.
+#[derive(Debug)]
+pub struct LoxFunction {
+    pub name: Option<Rc<str>>,
+    pub arity: u8,
+    pub chunk: Chunk,
+}
+
+impl Display for LoxFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "<fun {}>", name),
+            None => write!(f, "<script>"),
+        }
+    }
+}
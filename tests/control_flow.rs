@@ -0,0 +1,116 @@
+use std::{fs, path::PathBuf};
+
+use lox::{interpreter::Interpreter, parser::Parser, resolver::Resolver, user};
+
+mod helpers;
+use helpers::multi_test::MultiTest;
+
+/// Writes `src` to a uniquely-named temp file and runs it through the full pipeline via
+/// `user::run_file`, the same entry point the CLI uses. There's no public way to read a global
+/// back out of an `Interpreter` or to capture `print`'s stdout, so tests assert on *control flow*
+/// instead: source embeds `if (<unexpected>) { var x = 1 / 0; }` guards, so a wrong computation
+/// surfaces as a `RuntimeError::ZeroDivision` (run_file returns `Ok(false)`) instead of `Ok(true)`.
+fn run(name: &str, src: &str) -> bool {
+    let mut path = PathBuf::from(std::env::temp_dir());
+    path.push(format!("rs_lox_control_flow_test_{}_{}.lox", std::process::id(), name));
+    fs::write(&path, src).unwrap();
+    let result = user::run_file(&path, None).unwrap();
+    let _ = fs::remove_file(&path);
+    result
+}
+
+/// Resolves `src` directly (skipping the parser's own errors, which aren't the point here) and
+/// reports whether it resolves without error, for the static-rejection cases.
+fn resolves_ok(src: &str) -> bool {
+    let (stmts, parse_errors) = Parser::new(src).parse();
+    assert!(parse_errors.is_empty(), "unexpected parse errors: {:?}", parse_errors);
+    let mut interpreter = Interpreter::new();
+    let globals = interpreter.global_names();
+    let resolver = Resolver::new(&mut interpreter, globals);
+    let (ok, _errors, _warnings) = resolver.resolve(&stmts);
+    ok
+}
+
+#[test]
+fn break_stops_the_loop_at_the_right_iteration() {
+    let ok = run(
+        "break",
+        r#"
+        var i = 0;
+        while (i < 10) {
+            if (i == 3) break;
+            i = i + 1;
+        }
+        if (i != 3) { var x = 1 / 0; }
+        "#,
+    );
+    assert!(ok, "expected the loop to stop with i == 3");
+}
+
+#[test]
+fn continue_skips_the_rest_of_the_body_but_keeps_looping() {
+    let ok = run(
+        "continue",
+        r#"
+        var i = 0;
+        var sum = 0;
+        while (i < 5) {
+            i = i + 1;
+            if (i == 3) continue;
+            sum = sum + i;
+        }
+        // 1 + 2 + 4 + 5, skipping 3.
+        if (sum != 12) { var x = 1 / 0; }
+        "#,
+    );
+    assert!(ok, "expected continue to skip only the i == 3 iteration");
+}
+
+#[test]
+fn break_and_continue_are_rejected_outside_a_loop() {
+    let mut mt: MultiTest = MultiTest::new();
+    mt.named_test("break", || {
+        assert!(!resolves_ok("break;"), "bare `break` outside a loop must be a resolve error");
+    });
+    mt.named_test("continue", || {
+        assert!(!resolves_ok("continue;"), "bare `continue` outside a loop must be a resolve error");
+    });
+    mt.named_test("break in if, still outside any loop", || {
+        assert!(
+            !resolves_ok("if (true) { break; }"),
+            "`break` nested in an `if` with no enclosing loop must still be rejected"
+        );
+    });
+}
+
+/// A function body starts a fresh loop nest (`resolve_function` resets `loop_depth` to 0), since
+/// at runtime a call's `ControlFlow` propagation is disconnected from the outer loop's
+/// `eval_while_stmt`. So a `break`/`continue` textually inside an outer loop, but inside a `fun`
+/// declared within that loop, must still be statically rejected.
+#[test]
+fn break_inside_a_function_declared_within_a_loop_is_still_rejected() {
+    assert!(!resolves_ok(
+        r#"
+        while (true) {
+            fun inner() {
+                break;
+            }
+        }
+        "#
+    ));
+}
+
+#[test]
+fn break_inside_a_loop_nested_in_a_function_inside_another_loop_is_allowed() {
+    assert!(resolves_ok(
+        r#"
+        while (true) {
+            fun inner() {
+                while (true) {
+                    break;
+                }
+            }
+        }
+        "#
+    ));
+}
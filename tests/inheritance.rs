@@ -0,0 +1,72 @@
+use std::{fs, path::PathBuf};
+
+use lox::user;
+
+/// See `tests/control_flow.rs`'s `run` for why this differential (zero-division-on-mismatch)
+/// technique is used instead of reading a value back or capturing stdout.
+fn run(name: &str, src: &str) -> bool {
+    let mut path = PathBuf::from(std::env::temp_dir());
+    path.push(format!("rs_lox_inheritance_test_{}_{}.lox", std::process::id(), name));
+    fs::write(&path, src).unwrap();
+    let result = user::run_file(&path, None).unwrap();
+    let _ = fs::remove_file(&path);
+    result
+}
+
+#[test]
+fn super_dot_method_dispatches_to_the_superclass_implementation() {
+    let ok = run(
+        "super_method",
+        r#"
+        class Animal {
+            speak() { return "..."; }
+        }
+        class Dog < Animal {
+            speak() { return "Woof, but also: " + super.speak(); }
+        }
+        if (Dog().speak() != "Woof, but also: ...") { var x = 1 / 0; }
+        "#,
+    );
+    assert!(ok);
+}
+
+#[test]
+fn an_inherited_static_method_is_callable_on_the_subclass() {
+    let ok = run(
+        "static_inherited",
+        r#"
+        class Animal {
+            class kingdom() { return "Animalia"; }
+        }
+        class Dog < Animal {}
+        if (Dog.kingdom() != "Animalia") { var x = 1 / 0; }
+        "#,
+    );
+    assert!(ok);
+}
+
+/// Regression test: a getter accessed through `super` must be auto-invoked and return its
+/// *value*, the same as a plain `this.foo`/`instance.foo` getter access — not a bound method
+/// object that happens to never get called.
+#[test]
+fn super_dot_getter_is_auto_invoked_like_any_other_getter() {
+    let ok = run(
+        "super_getter",
+        r#"
+        class Animal {
+            greeting { return "hi from Animal"; }
+        }
+        class Dog < Animal {
+            greeting { return super.greeting; }
+        }
+        if (Dog().greeting != "hi from Animal") { var x = 1 / 0; }
+        "#,
+    );
+    assert!(ok);
+}
+
+#[test]
+fn a_subclass_naming_itself_as_its_own_superclass_is_a_static_error() {
+    let ok = run("self_inherit", "class Oops < Oops {}\n");
+    assert!(!ok, "a class inheriting from itself must be rejected before it runs");
+}
@@ -0,0 +1,36 @@
+use lox::parser::Parser;
+
+/// With `paren_free_conditions` unset (the default), an `if`/`while` condition without
+/// parentheses is merely *recovered* from — it still parses, but reports a diagnostic.
+#[test]
+fn bare_condition_is_an_error_by_default() {
+    let (_, errors) = Parser::new("if x { print x; }").parse();
+    assert!(!errors.is_empty(), "a bare condition should still be flagged by default");
+}
+
+/// With the option set, the same source is valid syntax, not merely recovered-from.
+#[test]
+fn bare_condition_is_accepted_under_paren_free_conditions() {
+    let mut parser = Parser::new("if x { print x; }");
+    parser.options.paren_free_conditions = true;
+    let (_, errors) = parser.parse();
+    assert!(errors.is_empty(), "expected no errors, got: {:?}", errors);
+}
+
+#[test]
+fn bare_while_condition_is_accepted_under_paren_free_conditions() {
+    let mut parser = Parser::new("while x < 10 { x = x + 1; }");
+    parser.options.paren_free_conditions = true;
+    let (_, errors) = parser.parse();
+    assert!(errors.is_empty(), "expected no errors, got: {:?}", errors);
+}
+
+/// The restriction set while parsing a paren-free condition must still be cleared inside an
+/// explicit parenthesized group, so a call's argument list (itself group-like) isn't affected.
+#[test]
+fn a_parenthesized_condition_with_a_call_inside_still_works_under_paren_free_conditions() {
+    let mut parser = Parser::new("if (add(1, 2)) { print 1; }");
+    parser.options.paren_free_conditions = true;
+    let (_, errors) = parser.parse();
+    assert!(errors.is_empty(), "expected no errors, got: {:?}", errors);
+}
@@ -0,0 +1,53 @@
+use lox::parser::{error::ParseError, Parser};
+
+mod helpers;
+use helpers::multi_test::MultiTest;
+
+/// Two independent, unrelated missing-expression errors in the same file must both be reported;
+/// panic mode must not stay stuck after the first one recovers locally (i.e. without reaching a
+/// `synchronize()` statement boundary), or the second, unrelated error gets silently dropped.
+#[test]
+fn reports_every_missing_expression_not_just_the_first() {
+    let (_, errors) = Parser::new("var a = ; print ;").parse();
+    assert_eq!(
+        errors.len(),
+        2,
+        "expected both missing-expression errors to be reported, got: {:?}",
+        errors
+    );
+    for error in &errors {
+        assert!(
+            matches!(error, ParseError::UnexpectedToken { message, .. } if message == "Expected any expression"),
+            "unexpected error: {:?}",
+            error
+        );
+    }
+}
+
+/// Same regression, but with more than two recoveries in a row, and across a statement boundary,
+/// to make sure panic mode never outlives the single recovery that set it.
+#[test]
+fn reports_every_missing_expression_across_many_statements() {
+    let mut mt: MultiTest = MultiTest::new();
+    let cases: Vec<(&str, usize)> = vec![
+        ("var a = ;", 1),
+        ("var a = ; var b = ;", 2),
+        ("var a = ; var b = ; var c = ;", 3),
+        ("print ; print ; print ;", 3),
+        ("var a = ; print ; var b = ;", 3),
+    ];
+    for (src, expected_count) in cases {
+        mt.named_test(src, move || {
+            let (_, errors) = Parser::new(src).parse();
+            assert_eq!(
+                errors.len(),
+                expected_count,
+                "source {:?} reported {} error(s), expected {}: {:?}",
+                src,
+                errors.len(),
+                expected_count,
+                errors
+            );
+        });
+    }
+}
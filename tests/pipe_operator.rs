@@ -0,0 +1,81 @@
+use std::{fs, path::PathBuf};
+
+use lox::user;
+
+mod helpers;
+use helpers::multi_test::MultiTest;
+
+/// See `tests/control_flow.rs`'s `run` for why this differential (zero-division-on-mismatch)
+/// technique is used instead of reading a value back or capturing stdout.
+fn run(name: &str, src: &str) -> bool {
+    let mut path = PathBuf::from(std::env::temp_dir());
+    path.push(format!("rs_lox_pipe_operator_test_{}_{}.lox", std::process::id(), name));
+    fs::write(&path, src).unwrap();
+    let result = user::run_file(&path, None).unwrap();
+    let _ = fs::remove_file(&path);
+    result
+}
+
+#[test]
+fn pipes_a_single_value_into_a_named_function() {
+    let ok = run(
+        "single",
+        r#"
+        fun double(x) { return x * 2; }
+        if ((3 |> double) != 6) { var x = 1 / 0; }
+        "#,
+    );
+    assert!(ok);
+}
+
+#[test]
+fn chains_left_associatively() {
+    let ok = run(
+        "chain",
+        r#"
+        fun double(x) { return x * 2; }
+        if ((3 |> double |> double) != 12) { var x = 1 / 0; }
+        "#,
+    );
+    assert!(ok);
+}
+
+#[test]
+fn desugars_a_piped_call_by_prepending_the_piped_value_as_the_first_argument() {
+    let ok = run(
+        "call_desugar",
+        r#"
+        fun add(a, b) { return a + b; }
+        if ((3 |> add(4)) != 7) { var x = 1 / 0; }
+        "#,
+    );
+    assert!(ok);
+}
+
+#[test]
+fn mixed_forms_agree() {
+    let mut mt: MultiTest = MultiTest::new();
+    let cases: Vec<(&str, &str)> = vec![
+        (
+            "bare callee then call",
+            r#"
+            fun inc(x) { return x + 1; }
+            fun add(a, b) { return a + b; }
+            if ((3 |> inc |> add(10)) != 14) { var x = 1 / 0; }
+            "#,
+        ),
+        (
+            "call then bare callee",
+            r#"
+            fun add(a, b) { return a + b; }
+            fun inc(x) { return x + 1; }
+            if ((3 |> add(1) |> inc) != 5) { var x = 1 / 0; }
+            "#,
+        ),
+    ];
+    for (name, src) in cases {
+        mt.named_test(name, move || {
+            assert!(run(name, src), "case {:?} did not evaluate as expected", name);
+        });
+    }
+}